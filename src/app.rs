@@ -1,27 +1,70 @@
-use crate::config::store::{load_settings, save_score, save_settings};
-use crate::data::themes::get_theme;
-use crate::engine::input_processor::create_input_state;
-use crate::engine::stats_calculator::{calculate_consistency, calculate_stats};
+use crate::config::store::{get_scores, load_settings, save_score, save_settings};
+use crate::data::languages::{
+    find_language, get_language, language_load_errors, DEFAULT_LANGUAGE,
+};
+use crate::data::quotes::pick_quote;
+use crate::data::repo::{pick_excerpt, source_files};
+use crate::data::snippets::{pick_snippet, snippet_language};
+use crate::data::themes::{find_theme, get_theme, theme_load_errors, DEFAULT_THEME};
+use crate::engine::adaptive::{merge_key_stats, weakest_keys, MAX_TARGETS, RECENT_TESTS};
+use crate::engine::code_snippet::snippet_words;
+use crate::engine::custom_text::build_custom_words;
+use crate::engine::fail_conditions::check_failure;
+use crate::engine::funbox::apply_funbox;
+use crate::engine::input_processor::{
+    append_words, create_input_state_with_options, expects_indent, finish_input,
+};
+use crate::engine::stats_calculator::{
+    calculate_consistency, calculate_live_stats, calculate_stats, calculate_word_history,
+};
 use crate::engine::timer::{
-    create_timer, get_elapsed_seconds, start_timer, tick_timer,
+    create_timer, get_elapsed_ms_at, get_elapsed_seconds, start_timer, tick_timer,
 };
-use crate::engine::word_generator::generate_words;
+use crate::engine::word_generator::{generate_more_words, generate_words, random_seed, word_pool};
 use crate::screens::{menu, results, scores, test};
 use crate::types::*;
 use crate::ui::header::HEADER_HEIGHT;
-use crate::ui::results_chart::CHART_HEIGHT;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::layout::Rect;
 use ratatui::Frame;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::time::Instant;
 
+/// Words generated up front (and per refill) in time mode.
+const WORD_STREAM_CHUNK: usize = 100;
+/// Time mode refills the word list once fewer words than this remain.
+const WORD_STREAM_LOOKAHEAD: usize = 50;
+
 pub struct App {
     pub screen: Screen,
     pub config: TestConfig,
+    /// The theme `config` names, looked up when the config changes.
+    pub theme: Arc<ThemeColors>,
     // Test state
     pub phase: TestPhase,
     pub input_state: Option<InputState>,
     pub words: Vec<String>,
+    /// Seed the current test's words were generated from.
+    pub seed: u64,
+    /// The quote being typed in quote mode.
+    pub quote: Option<Quote>,
+    /// The snippet being typed in code mode.
+    pub snippet: Option<Snippet>,
+    /// The piece of a repository file being typed in code mode.
+    pub excerpt: Option<SourceExcerpt>,
+    /// Source files of the repository last practiced on, walked once rather
+    /// than on every restart.
+    repo_files: Option<(String, Vec<PathBuf>)>,
+    /// Custom text from the command line and the saved text it stands in for.
+    /// It is used for this run only and never written to the settings.
+    cli_text: Option<(String, String)>,
+    /// Shown on the test screen when the test isn't quite what was asked
+    /// for, e.g. a repository without source files, and on the menu when
+    /// settings or language packs couldn't be used.
+    pub notice: Option<String>,
+    /// Weak keys the current test's words were chosen to practice.
+    pub targets: Vec<KeyTarget>,
     pub timer: TimerState,
     pub current_stats: StatsSnapshot,
     pub wpm_history: Vec<f64>,
@@ -31,6 +74,8 @@ pub struct App {
     last_stats_tick: Option<Instant>,
     // Results
     pub result_wpm_history: Vec<f64>,
+    /// Results screen shows per-word bursts instead of the wpm chart.
+    pub show_word_history: bool,
     // Terminal size
     pub terminal_width: u16,
     pub terminal_height: u16,
@@ -38,20 +83,59 @@ pub struct App {
     pub menu_state: menu::MenuState,
     // Scores state
     pub scores_scroll: usize,
+    /// Saved scores, read once when the scores screen opens.
+    pub scores: Vec<ScoreEntry>,
+    /// Only scores typed in this language are listed.
+    pub scores_language: Option<String>,
     // Flag for quit
     pub should_quit: bool,
 }
 
 impl App {
-    pub fn new() -> Self {
-        let config = load_settings();
+    /// `custom_text` (from a file or piped stdin) switches straight to custom
+    /// mode, `repo_dir` to code mode on that directory's source files.
+    pub fn new(custom_text: Option<String>, repo_dir: Option<String>) -> Self {
+        let mut config = load_settings();
+        let mut cli_text = None;
+        if let Some(text) = custom_text {
+            config.mode = TestMode::Custom;
+            let saved = std::mem::replace(&mut config.custom_text, text.clone());
+            cli_text = Some((text, saved));
+        }
+        if let Some(dir) = repo_dir {
+            config.mode = TestMode::Code;
+            config.code_source = CodeSource::Repo;
+            config.repo_dir = Some(dir);
+        }
+        // A language pack or theme may have been removed since the settings
+        // were saved
+        let mut warnings = Vec::new();
+        if let Err(e) = find_language(&config.language) {
+            warnings.push(format!("{e} — using {DEFAULT_LANGUAGE}"));
+            config.language = DEFAULT_LANGUAGE.into();
+        }
+        if let Err(e) = find_theme(&config.theme) {
+            warnings.push(format!("{e} — using {DEFAULT_THEME}"));
+            config.theme = DEFAULT_THEME.into();
+        }
+        warnings.extend(language_load_errors().iter().map(|e| format!("languages/{e}")));
+        warnings.extend(theme_load_errors().iter().map(|e| format!("themes/{e}")));
         Self {
             screen: Screen::Menu,
             menu_state: menu::MenuState::new(&config),
+            theme: get_theme(&config.theme),
             config,
             phase: TestPhase::Idle,
             input_state: None,
             words: Vec::new(),
+            seed: 0,
+            quote: None,
+            snippet: None,
+            excerpt: None,
+            repo_files: None,
+            cli_text,
+            notice: (!warnings.is_empty()).then(|| warnings.join("; ")),
+            targets: Vec::new(),
             timer: create_timer(None),
             current_stats: StatsSnapshot::default(),
             wpm_history: Vec::new(),
@@ -59,9 +143,12 @@ impl App {
             restart_pending: false,
             last_stats_tick: None,
             result_wpm_history: Vec::new(),
+            show_word_history: false,
             terminal_width: 80,
             terminal_height: 24,
             scores_scroll: 0,
+            scores: Vec::new(),
+            scores_language: None,
             should_quit: false,
         }
     }
@@ -81,6 +168,12 @@ impl App {
         }
     }
 
+    pub fn handle_paste(&mut self, text: &str) {
+        if self.screen == Screen::Menu {
+            menu::handle_paste(&mut self.menu_state, text);
+        }
+    }
+
     pub fn handle_resize(&mut self, w: u16, h: u16) {
         self.terminal_width = w;
         self.terminal_height = h;
@@ -104,23 +197,28 @@ impl App {
             self.last_stats_tick = Some(now);
             if let Some(ref input) = self.input_state {
                 let elapsed = get_elapsed_seconds(&self.timer);
-                let stats = calculate_stats(input, elapsed);
+                let stats = calculate_live_stats(input, elapsed);
                 // Only record WPM history after 1s to avoid near-zero division spikes
                 if elapsed >= 1.0 {
                     self.wpm_history.push(stats.raw_wpm);
                 }
+                let failed = check_failure(&self.config, input, &stats, elapsed);
                 self.current_stats = stats;
+                if failed.is_some() {
+                    self.finish_test(failed);
+                    return;
+                }
             }
         }
 
         // Handle timer expiry (time mode)
         if self.timer.expired {
-            self.finish_test();
+            self.finish_test(None);
         }
     }
 
     pub fn render(&self, frame: &mut Frame) {
-        let theme = get_theme(&self.config.theme);
+        let theme: &ThemeColors = &self.theme;
         let area = frame.area();
 
         // Set background color
@@ -160,7 +258,7 @@ impl App {
                 }
             }
             Screen::Test => 12,
-            Screen::Results => 2 + 3 + 2 + 2 + CHART_HEIGHT + 2,
+            Screen::Results => self.result.as_ref().map_or(0, results::content_height),
             Screen::Scores => area.height.saturating_sub(4),
         };
         let w = max_width.min(area.width);
@@ -177,38 +275,111 @@ impl App {
         match result {
             menu::MenuAction::None => {}
             menu::MenuAction::Start(new_config) => {
-                save_settings(&new_config);
-                self.config = new_config;
+                save_settings(&self.settings_to_save(&new_config));
+                self.set_config(new_config);
                 self.start_test();
             }
             menu::MenuAction::UpdateConfig(new_config) => {
-                self.config = new_config;
+                self.set_config(new_config);
             }
             menu::MenuAction::Scores => {
+                self.scores = get_scores();
                 self.scores_scroll = 0;
                 self.screen = Screen::Scores;
             }
         }
     }
 
+    /// Looks the theme up again too: it may have been re-saved under the
+    /// same name in the theme editor.
+    fn set_config(&mut self, config: TestConfig) {
+        self.theme = get_theme(&config.theme);
+        self.config = config;
+    }
+
+    /// `config` as it should be saved: text from the command line is swapped
+    /// back for the saved text, unless it was edited in the menu since.
+    fn settings_to_save(&self, config: &TestConfig) -> TestConfig {
+        let mut config = config.clone();
+        let unedited = self.cli_text.as_ref().filter(|(cli, _)| config.custom_text == *cli);
+        if let Some((_, saved)) = unedited {
+            config.custom_text = saved.clone();
+        }
+        config
+    }
+
     // ── Test ────────────────────────────────────────────────────────
 
     fn start_test(&mut self) {
-        let count = match self.config.mode {
-            TestMode::Words => self.config.word_count as usize,
-            TestMode::Time => 100,
+        self.seed = self.config.seed.unwrap_or_else(random_seed);
+        self.targets = self.adaptive_targets();
+        self.start_test_with_seed();
+    }
+
+    /// The weakest keys and bigrams over recent tests in this language, when
+    /// adaptive practice is on. Funbox runs typed different text, so they
+    /// don't count.
+    fn adaptive_targets(&self) -> Vec<KeyTarget> {
+        if !self.config.adaptive || !matches!(self.config.mode, TestMode::Time | TestMode::Words) {
+            return Vec::new();
+        }
+        let scores: Vec<ScoreEntry> = get_scores()
+            .into_iter()
+            .filter(|s| s.funbox.is_none() && s.typed_language() == self.config.language)
+            .take(RECENT_TESTS)
+            .collect();
+        let keys = merge_key_stats(scores.iter().map(|s| &s.key_stats));
+        let bigrams = merge_key_stats(scores.iter().map(|s| &s.bigram_stats));
+        let pool = word_pool(&self.generator_config(0));
+        weakest_keys(&keys, &bigrams, &pool.words, MAX_TARGETS)
+    }
+
+    /// Starts a test from `self.seed`, e.g. to replay the words just typed.
+    fn start_test_with_seed(&mut self) {
+        self.quote = None;
+        self.snippet = None;
+        self.excerpt = None;
+        self.notice = None;
+        let words = match self.config.mode {
+            TestMode::Quote => {
+                let quote =
+                    pick_quote(&self.config.language, self.config.quote_length, self.seed);
+                self.quote = Some(quote.clone());
+                quote.text.split_whitespace().map(String::from).collect()
+            }
+            TestMode::Custom => build_custom_words(
+                &self.config.custom_text,
+                self.config.custom_shuffle,
+                self.config.custom_repeat,
+                self.seed,
+            ),
+            // One empty word to start typing into; the list grows as it goes
+            TestMode::Zen => vec![String::new()],
+            TestMode::Words => {
+                self.notice = self.filter_notice();
+                generate_words(&self.generator_config(self.config.word_count as usize))
+            }
+            TestMode::Time => {
+                self.notice = self.filter_notice();
+                generate_words(&self.generator_config(WORD_STREAM_CHUNK))
+            }
+            TestMode::Code => self.code_words(),
         };
-        let words = generate_words(&GeneratorConfig {
-            language: self.config.language.clone(),
-            count,
-            punctuation: self.config.punctuation,
-            numbers: self.config.numbers,
-        });
-        self.words = words.clone();
-        self.input_state = Some(create_input_state(&words));
+        let typed_words = self.funboxed(&words, 0);
+        self.words = words;
+        // Code typing rules (e.g. no lazy mode) come from the code's language
+        let language = get_language(self.code_language().unwrap_or(&self.config.language));
+        self.input_state = Some(create_input_state_with_options(
+            &typed_words,
+            self.config.input_options(language),
+        ));
         let limit = match self.config.mode {
             TestMode::Time => Some(self.config.time_limit),
-            TestMode::Words => None,
+            TestMode::Words
+            | TestMode::Quote
+            | TestMode::Custom
+            | TestMode::Zen
+            | TestMode::Code => None,
         };
         self.timer = create_timer(limit);
         self.phase = TestPhase::Idle;
@@ -220,6 +391,95 @@ impl App {
         self.screen = Screen::Test;
     }
 
+    /// Code from the chosen repository, or a bundled snippet. A repository
+    /// with nothing to type falls back to the snippets, with a notice.
+    fn code_words(&mut self) -> Vec<String> {
+        if let (CodeSource::Repo, Some(dir)) = (self.config.code_source, &self.config.repo_dir) {
+            let cached = self.repo_files.as_ref().is_some_and(|(cached, _)| cached == dir);
+            if !cached {
+                self.repo_files = Some((dir.clone(), source_files(Path::new(dir))));
+            }
+            let files = self.repo_files.as_ref().map_or(&[][..], |(_, files)| files);
+            let kind = self.config.excerpt_kind;
+            let strip = self.config.strip_comments;
+            match pick_excerpt(Path::new(dir), files, kind, strip, self.seed) {
+                Ok(excerpt) => {
+                    let words = snippet_words(&excerpt.code);
+                    self.excerpt = Some(excerpt);
+                    return words;
+                }
+                Err(e) => self.notice = Some(format!("{e} — using bundled snippets")),
+            }
+        }
+        let snippet = pick_snippet(&self.config.language, self.seed);
+        self.snippet = Some(snippet.clone());
+        snippet_words(&snippet.code)
+    }
+
+    /// Language of the code being typed in code mode.
+    fn code_language(&self) -> Option<&str> {
+        match (&self.excerpt, &self.snippet) {
+            (Some(excerpt), _) => Some(&excerpt.language),
+            (None, Some(_)) => Some(snippet_language(&self.config.language)),
+            (None, None) => None,
+        }
+    }
+
+    /// Says which parts of the word filter were dropped for matching too
+    /// few words.
+    fn filter_notice(&self) -> Option<String> {
+        let relaxed = word_pool(&self.generator_config(0)).relaxed;
+        if relaxed.is_empty() {
+            return None;
+        }
+        let labels: Vec<&str> = relaxed.iter().map(|r| r.label()).collect();
+        Some(format!("too few words match the filter — ignoring {}", labels.join(", ")))
+    }
+
+    fn generator_config(&self, count: usize) -> GeneratorConfig {
+        GeneratorConfig {
+            language: self.config.language.clone(),
+            count,
+            punctuation: self.config.punctuation,
+            numbers: self.config.numbers,
+            seed: Some(self.seed),
+            targets: self.targets.iter().map(|t| t.key.clone()).collect(),
+            list_size: self.config.word_list,
+            filter: self.config.word_filter.clone(),
+        }
+    }
+
+    /// Applies the funbox to freshly generated words. `offset` (words already
+    /// in the test) keeps each chunk of a word stream reproducible from the seed.
+    /// `self.words` keeps the plain words so the generator can continue them.
+    fn funboxed(&self, words: &[String], offset: usize) -> Vec<String> {
+        match self.config.funbox {
+            Some(funbox) if !matches!(self.config.mode, TestMode::Zen | TestMode::Code) => {
+                apply_funbox(words, funbox, self.seed.wrapping_add(offset as u64))
+            }
+            _ => words.to_vec(),
+        }
+    }
+
+    /// Keeps time-mode tests from running out of words by appending another
+    /// chunk as the typist approaches the end of the list.
+    fn extend_word_stream(&mut self) {
+        if self.config.mode != TestMode::Time {
+            return;
+        }
+        let Some(state) = self.input_state.take() else {
+            return;
+        };
+        if state.words.len() - state.current_word_index > WORD_STREAM_LOOKAHEAD {
+            self.input_state = Some(state);
+            return;
+        }
+        let more = generate_more_words(&self.generator_config(WORD_STREAM_CHUNK), &self.words);
+        let typed_more = self.funboxed(&more, self.words.len());
+        self.words.extend(more);
+        self.input_state = Some(append_words(state, &typed_more));
+    }
+
     fn handle_test_key(&mut self, key: KeyEvent) {
         if key.code == KeyCode::Esc {
             self.restart_pending = false;
@@ -228,7 +488,11 @@ impl App {
             return;
         }
 
-        if key.code == KeyCode::Tab {
+        // Code mode types indentation with Tab; everywhere else it restarts
+        let typing_indent = self.config.mode == TestMode::Code
+            && !self.restart_pending
+            && self.input_state.as_ref().is_some_and(expects_indent);
+        if key.code == KeyCode::Tab && !typing_indent {
             self.restart_pending = true;
             return;
         }
@@ -247,14 +511,20 @@ impl App {
             return;
         }
 
-        let is_backspace = key.code == KeyCode::Backspace || key.code == KeyCode::Delete;
-        let is_ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
-
-        // Block backspace when disabled in config
-        if is_backspace && !self.config.backspace {
+        if self.config.mode == TestMode::Zen && self.is_zen_end_key(key) {
+            if self.phase == TestPhase::Active {
+                if let Some(state) = self.input_state.take() {
+                    let elapsed_ms = get_elapsed_ms_at(&self.timer, Instant::now());
+                    self.input_state = Some(finish_input(state, elapsed_ms));
+                }
+                self.finish_test(None);
+            }
             return;
         }
 
+        let is_backspace = key.code == KeyCode::Backspace || key.code == KeyCode::Delete;
+        let is_ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+
         let input = match key.code {
             KeyCode::Char(c) => {
                 if is_ctrl {
@@ -263,31 +533,61 @@ impl App {
                 c.to_string()
             }
             KeyCode::Backspace | KeyCode::Delete => String::new(),
+            KeyCode::Enter if self.config.mode == TestMode::Code => "\n".to_string(),
+            KeyCode::Tab => "\t".to_string(),
             _ => return,
         };
 
         if let Some(state) = self.input_state.take() {
-            let new_state =
-                crate::engine::input_processor::process_keystroke(state, &input, is_backspace, is_ctrl);
+            let now = Instant::now();
 
             // Start on first keystroke
             if self.phase == TestPhase::Idle {
                 self.phase = TestPhase::Active;
-                self.timer = start_timer(self.timer.clone(), Instant::now());
+                self.timer = start_timer(self.timer.clone(), now);
             }
 
-            // In words mode, check if all words are completed
-            if self.config.mode == TestMode::Words && new_state.finished {
+            let new_state = crate::engine::input_processor::process_keystroke(
+                state,
+                &input,
+                is_backspace,
+                is_ctrl,
+                get_elapsed_ms_at(&self.timer, now),
+            );
+
+            // Sudden death and accuracy can fail a test between stat ticks
+            let elapsed = get_elapsed_ms_at(&self.timer, now) / 1000.0;
+            let stats = calculate_live_stats(&new_state, elapsed);
+            if let Some(reason) = check_failure(&self.config, &new_state, &stats, elapsed) {
                 self.input_state = Some(new_state);
-                self.finish_test();
+                self.finish_test(Some(reason));
+                return;
+            }
+
+            // In words and quote mode, check if all words are completed
+            if self.config.mode != TestMode::Time && new_state.finished {
+                self.input_state = Some(new_state);
+                self.finish_test(None);
                 return;
             }
 
             self.input_state = Some(new_state);
+            self.extend_word_stream();
         }
     }
 
-    fn finish_test(&mut self) {
+    fn is_zen_end_key(&self, key: KeyEvent) -> bool {
+        let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+        match self.config.zen_end_key {
+            ZenEndKey::Enter => key.code == KeyCode::Enter,
+            ZenEndKey::ShiftEnter => key.code == KeyCode::Enter && shift,
+            ZenEndKey::CtrlD => {
+                key.code == KeyCode::Char('d') && key.modifiers.contains(KeyModifiers::CONTROL)
+            }
+        }
+    }
+
+    fn finish_test(&mut self, failed: Option<FailReason>) {
         self.phase = TestPhase::Finished;
         if let Some(ref input) = self.input_state {
             let elapsed = get_elapsed_seconds(&self.timer);
@@ -317,12 +617,24 @@ impl App {
                 total_words,
                 correct_words,
                 elapsed_seconds: elapsed,
+                keystrokes: input.keystrokes.clone(),
+                key_stats: stats.key_stats,
+                bigram_stats: stats.bigram_stats,
+                word_history: calculate_word_history(input),
+                failed,
+                seed: self.seed,
+                quote: self.quote.clone(),
+                snippet: self.snippet.clone(),
+                excerpt: self.excerpt.clone(),
+                source_language: self.code_language().map(String::from),
+                targets: self.targets.clone(),
                 config: self.config.clone(),
             };
 
             save_score(&result);
             self.result = Some(result);
             self.result_wpm_history = self.wpm_history.clone();
+            self.show_word_history = false;
             self.screen = Screen::Results;
         }
     }
@@ -332,6 +644,8 @@ impl App {
     fn handle_results_key(&mut self, key: KeyEvent) {
         match key.code {
             KeyCode::Tab => self.start_test(),
+            KeyCode::Char('r') if self.config.mode != TestMode::Zen => self.start_test_with_seed(),
+            KeyCode::Char('h') => self.show_word_history = !self.show_word_history,
             KeyCode::Esc => {
                 self.screen = Screen::Menu;
                 self.menu_state = menu::MenuState::new(&self.config);
@@ -343,7 +657,7 @@ impl App {
     // ── Scores ──────────────────────────────────────────────────────
 
     fn handle_scores_key(&mut self, key: KeyEvent) {
-        let scores = crate::config::store::get_scores();
+        let scores = scores::filtered_scores(&self.scores, self.scores_language.as_deref());
         let visible_rows = self.terminal_height.saturating_sub(8).max(5) as usize;
 
         match key.code {
@@ -358,6 +672,11 @@ impl App {
                 let max = scores.len().saturating_sub(visible_rows);
                 self.scores_scroll = (self.scores_scroll + 1).min(max);
             }
+            KeyCode::Char('l') => {
+                self.scores_language =
+                    scores::next_language_filter(&self.scores, self.scores_language.as_deref());
+                self.scores_scroll = 0;
+            }
             _ => {}
         }
     }
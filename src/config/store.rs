@@ -1,30 +1,60 @@
-use crate::types::{ScoreEntry, TestConfig, TestResult};
+use crate::types::{Confidence, KeystrokeEvent, ScoreEntry, ScoreStatus, TestConfig, TestResult};
 use directories::ProjectDirs;
+use std::collections::HashSet;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-fn config_dir() -> PathBuf {
+pub fn config_dir() -> PathBuf {
     ProjectDirs::from("", "", "buddytype")
         .map(|d| d.config_dir().to_path_buf())
         .unwrap_or_else(|| PathBuf::from("."))
 }
 
+/// Scores kept in the config directory.
+const MAX_SCORES: usize = 100;
+
 fn settings_path() -> PathBuf {
     config_dir().join("settings.json")
 }
 
-fn scores_path() -> PathBuf {
-    config_dir().join("scores.json")
+fn scores_path(dir: &Path) -> PathBuf {
+    dir.join("scores.json")
+}
+
+/// Keystroke logs are kept one file per score, out of `scores.json`, so
+/// listing scores doesn't parse them.
+fn keystrokes_dir(dir: &Path) -> PathBuf {
+    dir.join("keystrokes")
+}
+
+/// Folder of user language packs, MonkeyType-compatible word lists.
+pub fn languages_dir() -> PathBuf {
+    config_dir().join("languages")
+}
+
+/// Folder of user themes, one JSON or TOML file per theme.
+pub fn themes_dir() -> PathBuf {
+    config_dir().join("themes")
 }
 
 pub fn load_settings() -> TestConfig {
     let path = settings_path();
     match fs::read_to_string(&path) {
-        Ok(json) => serde_json::from_str(&json).unwrap_or_default(),
+        Ok(json) => settings_from_json(&json),
         Err(_) => TestConfig::default(),
     }
 }
 
+/// Parses saved settings; the old `backspace: false` becomes
+/// [`Confidence::Max`].
+pub fn settings_from_json(json: &str) -> TestConfig {
+    let mut config: TestConfig = serde_json::from_str(json).unwrap_or_default();
+    if config.backspace.take() == Some(false) {
+        config.confidence = Confidence::Max;
+    }
+    config
+}
+
 pub fn save_settings(config: &TestConfig) {
     let path = settings_path();
     if let Some(parent) = path.parent() {
@@ -46,27 +76,119 @@ pub fn save_score(result: &TestResult) {
         duration: match result.config.mode {
             crate::types::TestMode::Time => result.config.time_limit,
             crate::types::TestMode::Words => result.config.word_count,
+            crate::types::TestMode::Quote => result
+                .quote
+                .as_ref()
+                .map_or(0, |q| q.text.split_whitespace().count() as u32),
+            crate::types::TestMode::Custom => {
+                let words = result.config.custom_text.split_whitespace().count() as u32;
+                words * result.config.custom_repeat.max(1)
+            }
+            crate::types::TestMode::Zen => result.elapsed_seconds.round() as u32,
+            crate::types::TestMode::Code => {
+                let code = match (&result.snippet, &result.excerpt) {
+                    (Some(snippet), _) => snippet.code.as_str(),
+                    (None, Some(excerpt)) => excerpt.code.as_str(),
+                    (None, None) => "",
+                };
+                code.split_whitespace().count() as u32
+            }
         },
         date: chrono::Local::now().to_rfc3339(),
+        status: match result.failed {
+            Some(_) => ScoreStatus::Failed,
+            None => ScoreStatus::Completed,
+        },
+        fail_reason: result.failed,
+        seed: Some(result.seed),
+        quote_id: result.quote.as_ref().map(|q| q.id),
+        funbox: result.config.funbox,
+        source_language: result.source_language.clone(),
+        keystroke_log: None,
+        keystrokes: Vec::new(),
+        key_stats: result.key_stats.clone(),
+        bigram_stats: result.bigram_stats.clone(),
     };
+    store_score(&config_dir(), entry, &result.keystrokes);
+}
 
-    let mut scores = get_scores();
-    scores.insert(0, entry);
-    scores.truncate(100);
+/// Adds `entry` to the scores in `dir`, newest first, with its keystroke log
+/// in a file of its own. Logs of scores that no longer fit are removed, unless
+/// the scores couldn't be read and it isn't known which logs they refer to.
+pub fn store_score(dir: &Path, mut entry: ScoreEntry, keystrokes: &[KeystrokeEvent]) {
+    let logs = keystrokes_dir(dir);
+    let _ = fs::create_dir_all(&logs);
+    entry.keystrokes = keystrokes.to_vec();
 
-    let path = scores_path();
-    if let Some(parent) = path.parent() {
-        let _ = fs::create_dir_all(parent);
+    let loaded = load_scores(dir);
+    let known = loaded.is_ok();
+    let mut scores = loaded.unwrap_or_default();
+    scores.insert(0, entry);
+    scores.truncate(MAX_SCORES);
+    // Scores saved before logs had their own files still carry them
+    for score in scores.iter_mut().filter(|s| !s.keystrokes.is_empty()) {
+        let name = log_file_name(&score.date);
+        let written = serde_json::to_string(&score.keystrokes)
+            .is_ok_and(|json| fs::write(logs.join(&name), json).is_ok());
+        if written {
+            score.keystroke_log = Some(name);
+        }
+        score.keystrokes.clear();
     }
+
     if let Ok(json) = serde_json::to_string_pretty(&scores) {
-        let _ = fs::write(&path, json);
+        let _ = fs::write(scores_path(dir), json);
+    }
+
+    if !known {
+        return;
     }
+    let kept: HashSet<&str> = scores.iter().filter_map(|s| s.keystroke_log.as_deref()).collect();
+    for file in fs::read_dir(&logs).into_iter().flatten().flatten() {
+        let name = file.file_name();
+        if !kept.contains(name.to_string_lossy().as_ref()) {
+            let _ = fs::remove_file(file.path());
+        }
+    }
+}
+
+/// A log's file name, from the date of its score.
+fn log_file_name(date: &str) -> String {
+    let stem: String =
+        date.chars().map(|c| if c.is_ascii_alphanumeric() { c } else { '-' }).collect();
+    format!("{stem}.json")
 }
 
 pub fn get_scores() -> Vec<ScoreEntry> {
-    let path = scores_path();
-    match fs::read_to_string(&path) {
-        Ok(json) => serde_json::from_str(&json).unwrap_or_default(),
-        Err(_) => Vec::new(),
+    read_scores(&config_dir())
+}
+
+/// Scores saved in `dir`, newest first.
+pub fn read_scores(dir: &Path) -> Vec<ScoreEntry> {
+    load_scores(dir).unwrap_or_default()
+}
+
+/// Scores saved in `dir`; none if nothing was saved yet, an error if the
+/// file is there but can't be read.
+fn load_scores(dir: &Path) -> Result<Vec<ScoreEntry>, String> {
+    match fs::read_to_string(scores_path(dir)) {
+        Ok(json) => serde_json::from_str(&json).map_err(|e| e.to_string()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// The keystroke log of a score saved in `dir`, for analysing or replaying
+/// the session. Empty if it wasn't kept.
+#[allow(dead_code)] // used by tests
+pub fn read_keystrokes(dir: &Path, entry: &ScoreEntry) -> Vec<KeystrokeEvent> {
+    if !entry.keystrokes.is_empty() {
+        return entry.keystrokes.clone();
     }
+    entry
+        .keystroke_log
+        .as_ref()
+        .and_then(|name| fs::read_to_string(keystrokes_dir(dir).join(name)).ok())
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
 }
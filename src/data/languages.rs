@@ -1,8 +1,20 @@
-use crate::types::Language;
+use crate::config::store::languages_dir;
+use crate::types::{Language, WordListSize};
 use std::collections::HashMap;
+use std::path::Path;
 use std::sync::LazyLock;
 
-static LANGUAGES: LazyLock<HashMap<String, Language>> = LazyLock::new(|| {
+/// Falls back to this language when a requested one is missing.
+pub const DEFAULT_LANGUAGE: &str = "english";
+
+/// Built-in languages merged with the user's language packs, and the packs
+/// that failed to load.
+struct Registry {
+    languages: HashMap<String, Language>,
+    errors: Vec<String>,
+}
+
+static REGISTRY: LazyLock<Registry> = LazyLock::new(|| {
     let files: &[(&str, &str)] = &[
         ("code_go", include_str!("../../data/languages/code_go.json")),
         (
@@ -22,33 +34,181 @@ static LANGUAGES: LazyLock<HashMap<String, Language>> = LazyLock::new(|| {
             include_str!("../../data/languages/code_typescript.json"),
         ),
         ("english", include_str!("../../data/languages/english.json")),
+        (
+            "english_1k",
+            include_str!("../../data/languages/english_1k.json"),
+        ),
+        (
+            "english_5k",
+            include_str!("../../data/languages/english_5k.json"),
+        ),
+        (
+            "english_10k",
+            include_str!("../../data/languages/english_10k.json"),
+        ),
         ("french", include_str!("../../data/languages/french.json")),
+        (
+            "french_1k",
+            include_str!("../../data/languages/french_1k.json"),
+        ),
+        (
+            "french_5k",
+            include_str!("../../data/languages/french_5k.json"),
+        ),
+        (
+            "french_10k",
+            include_str!("../../data/languages/french_10k.json"),
+        ),
         ("german", include_str!("../../data/languages/german.json")),
+        (
+            "german_1k",
+            include_str!("../../data/languages/german_1k.json"),
+        ),
+        (
+            "german_5k",
+            include_str!("../../data/languages/german_5k.json"),
+        ),
+        (
+            "german_10k",
+            include_str!("../../data/languages/german_10k.json"),
+        ),
         ("italian", include_str!("../../data/languages/italian.json")),
+        (
+            "italian_1k",
+            include_str!("../../data/languages/italian_1k.json"),
+        ),
+        (
+            "italian_5k",
+            include_str!("../../data/languages/italian_5k.json"),
+        ),
+        (
+            "italian_10k",
+            include_str!("../../data/languages/italian_10k.json"),
+        ),
         (
             "portuguese",
             include_str!("../../data/languages/portuguese.json"),
         ),
+        (
+            "portuguese_1k",
+            include_str!("../../data/languages/portuguese_1k.json"),
+        ),
+        (
+            "portuguese_5k",
+            include_str!("../../data/languages/portuguese_5k.json"),
+        ),
+        (
+            "portuguese_10k",
+            include_str!("../../data/languages/portuguese_10k.json"),
+        ),
         ("spanish", include_str!("../../data/languages/spanish.json")),
+        (
+            "spanish_1k",
+            include_str!("../../data/languages/spanish_1k.json"),
+        ),
+        (
+            "spanish_5k",
+            include_str!("../../data/languages/spanish_5k.json"),
+        ),
+        (
+            "spanish_10k",
+            include_str!("../../data/languages/spanish_10k.json"),
+        ),
     ];
 
-    let mut map = HashMap::new();
+    let mut languages = HashMap::new();
     for (name, json) in files {
         let lang: Language =
             serde_json::from_str(json).unwrap_or_else(|e| panic!("Failed to parse {name}: {e}"));
-        map.insert(name.to_string(), lang);
+        languages.insert(name.to_string(), lang);
     }
-    map
+
+    // User packs may also replace a built-in list
+    let (packs, errors) = load_language_packs(&languages_dir());
+    languages.extend(packs);
+    Registry { languages, errors }
 });
 
-pub fn get_language(name: &str) -> &'static Language {
-    LANGUAGES
+/// Reads every `.json` word list in `dir`, named after its file. Lists that
+/// don't parse or have no words are reported instead. A missing folder has
+/// no packs.
+pub fn load_language_packs(dir: &Path) -> (Vec<(String, Language)>, Vec<String>) {
+    let mut packs = Vec::new();
+    let mut errors = Vec::new();
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return (packs, errors);
+    };
+    let mut paths: Vec<_> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|e| e == "json"))
+        .collect();
+    paths.sort();
+    for path in paths {
+        let Some(name) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        let file = path.file_name().unwrap_or_default().to_string_lossy();
+        let parsed = std::fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|json| serde_json::from_str::<Language>(&json).map_err(|e| e.to_string()));
+        match parsed {
+            Ok(lang) if lang.words.is_empty() => errors.push(format!("{file}: no words")),
+            Ok(lang) => packs.push((name.to_string(), lang)),
+            Err(e) => errors.push(format!("{file}: {e}")),
+        }
+    }
+    (packs, errors)
+}
+
+/// User language packs that couldn't be loaded, as `file: reason`.
+pub fn language_load_errors() -> &'static [String] {
+    &REGISTRY.errors
+}
+
+pub fn find_language(name: &str) -> Result<&'static Language, String> {
+    REGISTRY
+        .languages
         .get(name)
-        .unwrap_or_else(|| panic!("Unknown language: {name}"))
+        .ok_or_else(|| format!("unknown language \"{name}\""))
+}
+
+/// The named language, or english if there is no such language (e.g. a
+/// removed language pack still named in the settings).
+pub fn get_language(name: &str) -> &'static Language {
+    find_language(name).unwrap_or_else(|_| &REGISTRY.languages[DEFAULT_LANGUAGE])
 }
 
+/// Languages to pick from. Bigger word list variants (`english_1k`) are
+/// chosen by list size instead.
 pub fn get_available_languages() -> Vec<&'static str> {
-    let mut names: Vec<&str> = LANGUAGES.keys().map(String::as_str).collect();
+    let mut names: Vec<&str> = REGISTRY
+        .languages
+        .keys()
+        .map(String::as_str)
+        .filter(|name| variant_base(name).is_none())
+        .collect();
     names.sort();
     names
 }
+
+/// The base language a variant name like `english_1k` belongs to.
+fn variant_base(name: &str) -> Option<&str> {
+    WordListSize::ALL[1..].iter().find_map(|size| {
+        let base = name.strip_suffix(size.suffix())?;
+        REGISTRY.languages.contains_key(base).then_some(base)
+    })
+}
+
+/// The `size` word list of a language, if it has one.
+pub fn get_word_list(language: &str, size: WordListSize) -> Option<&'static Language> {
+    REGISTRY.languages.get(&format!("{language}{}", size.suffix()))
+}
+
+/// List sizes a language comes in, smallest first.
+pub fn available_list_sizes(language: &str) -> Vec<WordListSize> {
+    WordListSize::ALL
+        .into_iter()
+        .filter(|&size| get_word_list(language, size).is_some())
+        .collect()
+}
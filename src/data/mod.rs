@@ -1,2 +1,6 @@
 pub mod languages;
+pub mod palette;
+pub mod quotes;
+pub mod repo;
+pub mod snippets;
 pub mod themes;
@@ -0,0 +1,143 @@
+use ratatui::style::Color;
+use std::sync::LazyLock;
+
+/// How many colors the terminal can show.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorSupport {
+    TrueColor,
+    Ansi256,
+    Ansi16,
+    /// `NO_COLOR` or a dumb terminal: styles tell characters apart instead.
+    Monochrome,
+}
+
+static COLOR_SUPPORT: LazyLock<ColorSupport> =
+    LazyLock::new(|| detect_color_support(|name| std::env::var(name).ok()));
+
+/// The color support of the terminal we run in, detected once.
+pub fn color_support() -> ColorSupport {
+    *COLOR_SUPPORT
+}
+
+/// Works out color support from environment variables, read through `var`.
+/// `BUDDYTYPE_COLORS` (`truecolor`, `256`, `16` or `none`) overrides the
+/// guess. `COLORTERM` is trusted inside tmux too, since it is the way tmux
+/// says it passes 24-bit color through; without it tmux gets 256 colors.
+pub fn detect_color_support(var: impl Fn(&str) -> Option<String>) -> ColorSupport {
+    let set = |name: &str| var(name).filter(|v| !v.is_empty());
+    match set("BUDDYTYPE_COLORS").map(|v| v.to_lowercase()).as_deref() {
+        Some("truecolor" | "24bit") => return ColorSupport::TrueColor,
+        Some("256") => return ColorSupport::Ansi256,
+        Some("16") => return ColorSupport::Ansi16,
+        Some("none" | "mono") => return ColorSupport::Monochrome,
+        _ => {}
+    }
+    if set("NO_COLOR").is_some() {
+        return ColorSupport::Monochrome;
+    }
+    let term = set("TERM").unwrap_or_default();
+    if term == "dumb" {
+        return ColorSupport::Monochrome;
+    }
+    let colorterm = set("COLORTERM").unwrap_or_default().to_lowercase();
+    if colorterm == "truecolor" || colorterm == "24bit" || term.ends_with("-direct") {
+        return ColorSupport::TrueColor;
+    }
+    if set("TMUX").is_some() || term.starts_with("tmux") || term.starts_with("screen") {
+        return if term.contains("256color") || term.starts_with("tmux") {
+            ColorSupport::Ansi256
+        } else {
+            ColorSupport::Ansi16
+        };
+    }
+    match set("TERM_PROGRAM").as_deref() {
+        Some("iTerm.app" | "WezTerm" | "vscode") => return ColorSupport::TrueColor,
+        Some("Apple_Terminal") => return ColorSupport::Ansi256,
+        _ => {}
+    }
+    if term.contains("256color") {
+        ColorSupport::Ansi256
+    } else if term.is_empty() {
+        // Windows consoles set no TERM and have had 24-bit color for years
+        ColorSupport::TrueColor
+    } else {
+        ColorSupport::Ansi16
+    }
+}
+
+/// Channel levels of the 6×6×6 color cube in the 256-color palette.
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+fn distance((r1, g1, b1): (u8, u8, u8), (r2, g2, b2): (u8, u8, u8)) -> u32 {
+    let d = |a: u8, b: u8| (i32::from(a) - i32::from(b)).pow(2) as u32;
+    d(r1, r2) + d(g1, g2) + d(b1, b2)
+}
+
+/// The closest entry of the 256-color palette's cube or gray ramp. The first
+/// 16 entries are left out, as terminals color them differently.
+pub fn nearest_256(r: u8, g: u8, b: u8) -> u8 {
+    let level = |c: u8| {
+        (0..CUBE_LEVELS.len())
+            .min_by_key(|&i| (i32::from(CUBE_LEVELS[i]) - i32::from(c)).abs())
+            .unwrap_or(0)
+    };
+    let (ri, gi, bi) = (level(r), level(g), level(b));
+    let cube = (CUBE_LEVELS[ri], CUBE_LEVELS[gi], CUBE_LEVELS[bi]);
+    let cube_index = 16 + 36 * ri + 6 * gi + bi;
+
+    // Gray ramp 232..=255 runs from 8 to 238 in steps of 10
+    let mean = (u32::from(r) + u32::from(g) + u32::from(b)) / 3;
+    let step = (mean.saturating_sub(3) / 10).min(23) as u8;
+    let gray = 8 + 10 * step;
+    if distance((gray, gray, gray), (r, g, b)) < distance(cube, (r, g, b)) {
+        232 + step
+    } else {
+        cube_index as u8
+    }
+}
+
+/// The 16 ANSI colors, at xterm's default values.
+const ANSI_16: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// Colors whose channels spread at least this far keep a hue in 16 colors;
+/// theme colors are often muted, and would otherwise all come out gray.
+const MIN_CHROMA: u8 = 64;
+
+/// The closest of the 16 ANSI colors, among the grays (black, the two grays
+/// and white) for grayish colors and among the rest otherwise.
+pub fn nearest_16(r: u8, g: u8, b: u8) -> Color {
+    let chromatic = r.max(g).max(b) - r.min(g).min(b) >= MIN_CHROMA;
+    ANSI_16
+        .iter()
+        .filter(|(_, (pr, pg, pb))| (pr == pg && pg == pb) != chromatic)
+        .min_by_key(|(_, rgb)| distance(*rgb, (r, g, b)))
+        .map_or(Color::Reset, |(color, _)| *color)
+}
+
+/// `color` as the terminal can show it. Monochrome terminals keep their own
+/// colors.
+pub fn adapt_color(color: Color, support: ColorSupport) -> Color {
+    match (color, support) {
+        (_, ColorSupport::Monochrome) => Color::Reset,
+        (Color::Rgb(r, g, b), ColorSupport::Ansi256) => Color::Indexed(nearest_256(r, g, b)),
+        (Color::Rgb(r, g, b), ColorSupport::Ansi16) => nearest_16(r, g, b),
+        _ => color,
+    }
+}
@@ -0,0 +1,65 @@
+use crate::types::{Quote, QuoteCollection, QuoteLength};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// Languages without a collection of their own (e.g. code) fall back to this.
+const FALLBACK_LANGUAGE: &str = "english";
+
+static QUOTES: LazyLock<HashMap<String, QuoteCollection>> = LazyLock::new(|| {
+    let files: &[(&str, &str)] = &[
+        ("english", include_str!("../../data/quotes/english.json")),
+        ("french", include_str!("../../data/quotes/french.json")),
+        ("german", include_str!("../../data/quotes/german.json")),
+        ("italian", include_str!("../../data/quotes/italian.json")),
+        (
+            "portuguese",
+            include_str!("../../data/quotes/portuguese.json"),
+        ),
+        ("spanish", include_str!("../../data/quotes/spanish.json")),
+    ];
+
+    let mut map = HashMap::new();
+    for (name, json) in files {
+        let quotes: QuoteCollection = serde_json::from_str(json)
+            .unwrap_or_else(|e| panic!("Failed to parse quotes for {name}: {e}"));
+        map.insert(name.to_string(), quotes);
+    }
+    map
+});
+
+pub fn get_quotes(language: &str) -> Option<&'static QuoteCollection> {
+    QUOTES.get(language)
+}
+
+/// Whether `language` has its own quotes rather than borrowing english ones.
+pub fn has_quotes(language: &str) -> bool {
+    QUOTES.contains_key(language)
+}
+
+/// Quotes of `collection` whose length falls in the given group.
+pub fn quotes_of_length(collection: &QuoteCollection, length: QuoteLength) -> Vec<&Quote> {
+    let Some(&[min, max]) = collection.groups.get(length.group_index()) else {
+        return Vec::new();
+    };
+    collection
+        .quotes
+        .iter()
+        .filter(|q| (min..=max).contains(&q.length()))
+        .collect()
+}
+
+/// Picks a quote for `language` deterministically from `seed`. If the
+/// language has no quote of the requested length, any of its quotes is used.
+pub fn pick_quote(language: &str, length: QuoteLength, seed: u64) -> &'static Quote {
+    let collection = get_quotes(language)
+        .or_else(|| get_quotes(FALLBACK_LANGUAGE))
+        .expect("english quotes are bundled");
+    let mut candidates = quotes_of_length(collection, length);
+    if candidates.is_empty() {
+        candidates = collection.quotes.iter().collect();
+    }
+    let mut rng = StdRng::seed_from_u64(seed);
+    candidates[rng.random_range(0..candidates.len())]
+}
@@ -0,0 +1,104 @@
+use crate::engine::source_code::{detect_language, extract_excerpt, strip_comments};
+use crate::types::{ExcerptKind, SourceExcerpt};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::path::{Path, PathBuf};
+
+/// Directories of dependencies, build output and tooling, never practiced on.
+const SKIPPED_DIRS: &[&str] = &[
+    ".git",
+    "target",
+    "node_modules",
+    "vendor",
+    "dist",
+    "build",
+    "__pycache__",
+    ".venv",
+];
+/// Stop scanning huge trees after this many source files.
+const MAX_FILES: usize = 20_000;
+/// Bigger files are most likely generated.
+const MAX_FILE_BYTES: u64 = 512 * 1024;
+/// Files tried before giving up on finding something to type.
+const MAX_ATTEMPTS: usize = 20;
+
+/// Source files in a known code language under `dir`, sorted so a seed
+/// always picks the same file. Each directory is walked in name order, so
+/// the same files are found when the walk stops at [`MAX_FILES`].
+pub fn source_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&current) else {
+            continue;
+        };
+        let mut entries: Vec<_> = entries.flatten().collect();
+        entries.sort_by_key(|entry| entry.file_name());
+        let mut subdirs = Vec::new();
+        for entry in entries {
+            let path = entry.path();
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                let name = entry.file_name();
+                let hidden = name.to_string_lossy().starts_with('.');
+                if !hidden && !SKIPPED_DIRS.contains(&name.to_string_lossy().as_ref()) {
+                    subdirs.push(path);
+                }
+            } else if file_type.is_file() && detect_language(&path).is_some() {
+                files.push(path);
+                if files.len() >= MAX_FILES {
+                    files.sort();
+                    return files;
+                }
+            }
+        }
+        // Popped in name order
+        pending.extend(subdirs.into_iter().rev());
+    }
+    files.sort();
+    files
+}
+
+/// Picks a function or line range from `files`, the [`source_files`] of
+/// `dir`.
+pub fn pick_excerpt(
+    dir: &Path,
+    files: &[PathBuf],
+    kind: ExcerptKind,
+    strip: bool,
+    seed: u64,
+) -> Result<SourceExcerpt, String> {
+    if !dir.is_dir() {
+        return Err(format!("{} is not a directory", dir.display()));
+    }
+    if files.is_empty() {
+        return Err(format!("no source files found in {}", dir.display()));
+    }
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    for _ in 0..MAX_ATTEMPTS.min(files.len()) {
+        let path = &files[rng.random_range(0..files.len())];
+        let too_big = std::fs::metadata(path).map_or(true, |m| m.len() > MAX_FILE_BYTES);
+        let Some(text) = (!too_big).then(|| std::fs::read_to_string(path).ok()).flatten() else {
+            continue;
+        };
+        let language = detect_language(path).expect("source files have a known language");
+        let Some((code, range)) = extract_excerpt(&text, language, kind, rng.random()) else {
+            continue;
+        };
+        let code = if strip { strip_comments(&code, language) } else { code };
+        if code.split_whitespace().next().is_none() {
+            continue;
+        }
+        let relative = path.strip_prefix(dir).unwrap_or(path);
+        return Ok(SourceExcerpt {
+            code,
+            path: relative.display().to_string(),
+            lines: (range.start + 1, range.end),
+            language: language.to_string(),
+        });
+    }
+    Err(format!("nothing to type in the source files of {}", dir.display()))
+}
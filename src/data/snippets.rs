@@ -0,0 +1,53 @@
+use crate::types::{Snippet, SnippetCollection};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+/// Languages without snippets of their own (e.g. english) fall back to this.
+const FALLBACK_LANGUAGE: &str = "code_rust";
+
+static SNIPPETS: LazyLock<HashMap<String, SnippetCollection>> = LazyLock::new(|| {
+    let files: &[(&str, &str)] = &[
+        ("code_go", include_str!("../../data/snippets/go.json")),
+        (
+            "code_javascript",
+            include_str!("../../data/snippets/javascript.json"),
+        ),
+        ("code_python", include_str!("../../data/snippets/python.json")),
+        ("code_rust", include_str!("../../data/snippets/rust.json")),
+        (
+            "code_typescript",
+            include_str!("../../data/snippets/typescript.json"),
+        ),
+    ];
+
+    let mut map = HashMap::new();
+    for (name, json) in files {
+        let snippets: SnippetCollection = serde_json::from_str(json)
+            .unwrap_or_else(|e| panic!("Failed to parse snippets for {name}: {e}"));
+        map.insert(name.to_string(), snippets);
+    }
+    map
+});
+
+pub fn get_snippets(language: &str) -> Option<&'static SnippetCollection> {
+    SNIPPETS.get(language)
+}
+
+/// The code language snippets for `language` are taken from: itself if it
+/// has any, otherwise rust.
+pub fn snippet_language(language: &str) -> &str {
+    if SNIPPETS.contains_key(language) {
+        language
+    } else {
+        FALLBACK_LANGUAGE
+    }
+}
+
+/// Picks a snippet for `language` deterministically from `seed`.
+pub fn pick_snippet(language: &str, seed: u64) -> &'static Snippet {
+    let collection = get_snippets(snippet_language(language)).expect("rust snippets are bundled");
+    let mut rng = StdRng::seed_from_u64(seed);
+    &collection.snippets[rng.random_range(0..collection.snippets.len())]
+}
@@ -1,9 +1,23 @@
+use crate::config::store::themes_dir;
+use crate::data::palette::{adapt_color, color_support};
 use crate::types::ThemeColors;
 use ratatui::style::Color;
 use std::collections::HashMap;
-use std::sync::LazyLock;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, Mutex};
 
-static THEMES: LazyLock<HashMap<String, ThemeColors>> = LazyLock::new(|| {
+/// Falls back to this theme when a requested one is missing.
+pub const DEFAULT_THEME: &str = "dark";
+
+/// Bundled themes, the user's theme files, and the files that failed to
+/// load.
+struct Registry {
+    bundled: HashMap<String, Arc<ThemeColors>>,
+    files: HashMap<String, Arc<ThemeColors>>,
+    errors: Vec<String>,
+}
+
+static REGISTRY: LazyLock<Registry> = LazyLock::new(|| {
     let json = include_str!("../../data/themes/monkeytype_themes.json");
     let mut themes: HashMap<String, ThemeColors> =
         serde_json::from_str(json).expect("Failed to parse themes JSON");
@@ -40,27 +54,192 @@ static THEMES: LazyLock<HashMap<String, ThemeColors>> = LazyLock::new(|| {
         },
     );
 
-    themes
+    let (files, errors) = load_theme_files(&themes_dir());
+    Registry {
+        bundled: themes.into_iter().map(|(name, theme)| (name, Arc::new(theme))).collect(),
+        files: files.into_iter().map(|(name, theme)| (name, Arc::new(theme))).collect(),
+        errors,
+    }
 });
 
-pub fn get_theme(name: &str) -> &'static ThemeColors {
-    THEMES.get(name).unwrap_or_else(|| &THEMES["dark"])
+/// Themes saved from the theme editor since startup, one per name.
+static SAVED_THEMES: Mutex<Vec<Arc<ThemeColors>>> = Mutex::new(Vec::new());
+
+fn saved_themes() -> std::sync::MutexGuard<'static, Vec<Arc<ThemeColors>>> {
+    SAVED_THEMES.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Reads every `.json` and `.toml` theme in `dir`, named after its file.
+/// Files that don't parse or hold invalid colors are reported instead. A
+/// missing folder has no themes.
+pub fn load_theme_files(dir: &Path) -> (Vec<(String, ThemeColors)>, Vec<String>) {
+    let mut themes = Vec::new();
+    let mut errors = Vec::new();
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return (themes, errors);
+    };
+    let mut paths: Vec<_> = entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|e| e == "json" || e == "toml"))
+        .collect();
+    paths.sort();
+    for path in paths {
+        let Some(name) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        let file = path.file_name().unwrap_or_default().to_string_lossy();
+        let toml = path.extension().is_some_and(|e| e == "toml");
+        let parsed = std::fs::read_to_string(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|text| parse_theme(&text, toml));
+        match parsed {
+            Ok(mut theme) => {
+                let invalid = invalid_colors(&theme);
+                if invalid.is_empty() {
+                    theme.name = name.to_string();
+                    themes.push((name.to_string(), theme));
+                } else {
+                    errors.push(format!("{file}: {}", invalid.join(", ")));
+                }
+            }
+            Err(e) => errors.push(format!("{file}: {e}")),
+        }
+    }
+    (themes, errors)
+}
+
+/// Parses a theme file's text, JSON or TOML.
+pub fn parse_theme(text: &str, toml: bool) -> Result<ThemeColors, String> {
+    if toml {
+        // The message alone; the full error quotes the file over several lines
+        toml::from_str(text).map_err(|e| e.message().to_string())
+    } else {
+        serde_json::from_str(text).map_err(|e| e.to_string())
+    }
+}
+
+/// `field "value"` for every color of `theme` that isn't a hex color.
+pub fn invalid_colors(theme: &ThemeColors) -> Vec<String> {
+    theme
+        .colors()
+        .iter()
+        .filter(|(_, value)| parse_color(value).is_err())
+        .map(|(field, value)| format!("{field} \"{value}\" is not a hex color"))
+        .collect()
+}
+
+/// Theme names become file names, so they keep to ASCII letters, digits,
+/// `_` and `-`. Bundled themes keep their names.
+pub fn check_theme_name(name: &str) -> Result<(), String> {
+    if name.is_empty() {
+        return Err("the theme needs a name".into());
+    }
+    if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+        return Err("names can only use letters, digits, _ and -".into());
+    }
+    if bundled_theme(name).is_some() {
+        return Err(format!("\"{name}\" is a built-in theme"));
+    }
+    Ok(())
+}
+
+/// Writes `theme` to `dir` as `<name>.json`, in the format theme files are
+/// loaded from, and returns its path.
+pub fn write_theme_file(dir: &Path, name: &str, theme: &ThemeColors) -> Result<PathBuf, String> {
+    check_theme_name(name)?;
+    let invalid = invalid_colors(theme);
+    if !invalid.is_empty() {
+        return Err(invalid.join(", "));
+    }
+    let colors: serde_json::Map<String, serde_json::Value> = theme
+        .colors()
+        .iter()
+        .map(|(field, value)| (field.to_string(), serde_json::Value::String(value.to_string())))
+        .collect();
+    let json = serde_json::to_string_pretty(&colors).map_err(|e| e.to_string())?;
+    std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
+    let path = dir.join(format!("{name}.json"));
+    std::fs::write(&path, json + "\n").map_err(|e| e.to_string())?;
+    Ok(path)
+}
+
+/// Saves `theme` to the user's themes folder as `name`, and lists it in the
+/// theme picker right away, replacing an earlier save of the same name.
+pub fn save_user_theme(name: &str, theme: &ThemeColors) -> Result<(), String> {
+    write_theme_file(&themes_dir(), name, theme)?;
+    let saved = Arc::new(ThemeColors {
+        name: name.to_string(),
+        ..theme.clone()
+    });
+    let mut themes = saved_themes();
+    match themes.iter_mut().find(|t| t.name == name) {
+        Some(old) => *old = saved,
+        None => themes.push(saved),
+    }
+    Ok(())
+}
+
+/// User theme files that couldn't be loaded, as `file: reason`.
+pub fn theme_load_errors() -> &'static [String] {
+    &REGISTRY.errors
 }
 
-pub fn get_theme_names() -> Vec<&'static str> {
-    let mut names: Vec<&str> = THEMES.keys().map(String::as_str).collect();
+/// A theme that ships with the app, untouched by the user's files.
+pub fn bundled_theme(name: &str) -> Option<&'static ThemeColors> {
+    REGISTRY.bundled.get(name).map(|theme| &**theme)
+}
+
+/// Themes saved in the editor come first, then theme files, then the bundled
+/// ones. Look a theme up when it's picked and keep it, not on every frame.
+pub fn find_theme(name: &str) -> Result<Arc<ThemeColors>, String> {
+    if let Some(theme) = saved_themes().iter().find(|t| t.name == name) {
+        return Ok(Arc::clone(theme));
+    }
+    REGISTRY
+        .files
+        .get(name)
+        .or_else(|| REGISTRY.bundled.get(name))
+        .cloned()
+        .ok_or_else(|| format!("unknown theme \"{name}\""))
+}
+
+pub fn get_theme(name: &str) -> Arc<ThemeColors> {
+    find_theme(name).unwrap_or_else(|_| Arc::clone(&REGISTRY.bundled[DEFAULT_THEME]))
+}
+
+pub fn get_theme_names() -> Vec<String> {
+    let mut names: Vec<String> =
+        REGISTRY.bundled.keys().chain(REGISTRY.files.keys()).cloned().collect();
+    names.extend(saved_themes().iter().map(|t| t.name.clone()));
     names.sort();
+    names.dedup();
     names
 }
 
-pub fn parse_hex(hex: &str) -> Color {
-    let h = hex.trim_start_matches('#');
-    if h.len() < 6 {
-        return Color::White;
+/// Reads `#rgb`, `#rrggbb` or `#rrggbbaa`. Terminals can't blend, so alpha
+/// is ignored.
+pub fn parse_color(hex: &str) -> Result<Color, String> {
+    let h = hex.strip_prefix('#').unwrap_or(hex);
+    let invalid = || format!("\"{hex}\" is not a hex color");
+    if !h.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(invalid());
     }
-    Color::Rgb(
-        u8::from_str_radix(&h[0..2], 16).unwrap_or(0),
-        u8::from_str_radix(&h[2..4], 16).unwrap_or(0),
-        u8::from_str_radix(&h[4..6], 16).unwrap_or(0),
-    )
+    let channel = |s: &str| u8::from_str_radix(s, 16).map_err(|_| invalid());
+    match h.len() {
+        3 => {
+            // #abc is #aabbcc
+            let digit = |i: usize| channel(&h[i..=i]).map(|v| v * 17);
+            Ok(Color::Rgb(digit(0)?, digit(1)?, digit(2)?))
+        }
+        6 | 8 => Ok(Color::Rgb(channel(&h[0..2])?, channel(&h[2..4])?, channel(&h[4..6])?)),
+        _ => Err(invalid()),
+    }
+}
+
+/// A theme color for rendering, brought down to what the terminal supports.
+/// Theme files are checked when loaded, so anything unreadable here falls
+/// back to the terminal's own color.
+pub fn parse_hex(hex: &str) -> Color {
+    adapt_color(parse_color(hex).unwrap_or(Color::Reset), color_support())
 }
@@ -0,0 +1,110 @@
+use crate::types::{KeyStats, KeyTarget};
+use std::collections::BTreeMap;
+
+/// Tests the weak keys are worked out from, most recent first.
+pub const RECENT_TESTS: usize = 20;
+/// Keys and bigrams pressed fewer times than this are not judged yet.
+pub const MIN_PRESSES: usize = 10;
+/// Weak keys and bigrams one test works on.
+pub const MAX_TARGETS: usize = 3;
+/// Weight of the error rate against slowness: 10% errors count as much as
+/// pressing a key 50% slower than usual.
+const ERROR_WEIGHT: f64 = 5.0;
+
+/// Adds up per-key stats from several tests, averaging press times over all
+/// of their timed presses.
+pub fn merge_key_stats<'a>(
+    tests: impl IntoIterator<Item = &'a BTreeMap<String, KeyStats>>,
+) -> BTreeMap<String, KeyStats> {
+    let mut merged: BTreeMap<String, KeyStats> = BTreeMap::new();
+    for stats in tests {
+        for (key, s) in stats {
+            let m = merged.entry(key.clone()).or_default();
+            m.correct += s.correct;
+            m.incorrect += s.incorrect;
+            for (typed, count) in &s.substitutions {
+                *m.substitutions.entry(typed.clone()).or_default() += count;
+            }
+            let timed = m.timed_presses + s.timed_presses;
+            if timed > 0 {
+                m.avg_press_ms = (m.avg_press_ms * m.timed_presses as f64
+                    + s.avg_press_ms * s.timed_presses as f64)
+                    / timed as f64;
+            }
+            m.timed_presses = timed;
+        }
+    }
+    merged
+}
+
+/// How much a key holds the typist back: its weighted error rate plus how
+/// much slower than `typical_ms` it is pressed. Faster than usual counts as 0.
+pub fn weakness(stats: &KeyStats, typical_ms: f64) -> f64 {
+    let slowness = if typical_ms > 0.0 && stats.timed_presses > 0 {
+        (stats.avg_press_ms / typical_ms - 1.0).max(0.0)
+    } else {
+        0.0
+    };
+    stats.error_rate() * ERROR_WEIGHT + slowness
+}
+
+/// Mean time of every timed press in `stats`.
+fn typical_press_ms(stats: &BTreeMap<String, KeyStats>) -> f64 {
+    let timed: usize = stats.values().map(|s| s.timed_presses).sum();
+    if timed == 0 {
+        return 0.0;
+    }
+    let total: f64 = stats.values().map(|s| s.avg_press_ms * s.timed_presses as f64).sum();
+    total / timed as f64
+}
+
+/// The weakest keys and bigrams, worst first, that some word in `words`
+/// can practice. Keys and bigrams are each judged against their own typical
+/// speed; a bigram overlapping an already chosen key is passed over.
+pub fn weakest_keys(
+    keys: &BTreeMap<String, KeyStats>,
+    bigrams: &BTreeMap<String, KeyStats>,
+    words: &[String],
+    count: usize,
+) -> Vec<KeyTarget> {
+    let mut candidates: Vec<(f64, &String, &KeyStats)> = Vec::new();
+    for stats in [keys, bigrams] {
+        let typical_ms = typical_press_ms(stats);
+        candidates.extend(
+            stats
+                .iter()
+                .filter(|(key, s)| s.presses() >= MIN_PRESSES && !key.trim().is_empty())
+                .filter(|(key, _)| words.iter().any(|w| w.contains(key.as_str())))
+                .map(|(key, s)| (weakness(s, typical_ms), key, s))
+                .filter(|(score, _, _)| *score > 0.0),
+        );
+    }
+    candidates.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.cmp(b.1)));
+
+    let mut targets: Vec<KeyTarget> = Vec::new();
+    for (_, key, stats) in candidates {
+        if targets.len() == count {
+            break;
+        }
+        let overlaps = targets
+            .iter()
+            .any(|t| t.key.contains(key.as_str()) || key.contains(t.key.as_str()));
+        if !overlaps {
+            targets.push(KeyTarget {
+                key: key.clone(),
+                error_rate: stats.error_rate(),
+                avg_press_ms: stats.avg_press_ms,
+            });
+        }
+    }
+    targets
+}
+
+/// Whether a target went better in the latest test: fewer errors, or as few
+/// and faster. `None` if the test never asked for it.
+pub fn improved(target: &KeyTarget, now: Option<&KeyStats>) -> Option<bool> {
+    let now = now.filter(|s| s.presses() > 0)?;
+    let error_rate = now.error_rate();
+    let faster = now.timed_presses > 0 && now.avg_press_ms < target.avg_press_ms;
+    Some(error_rate < target.error_rate || (error_rate <= target.error_rate && faster))
+}
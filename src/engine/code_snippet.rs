@@ -0,0 +1,46 @@
+/// Columns a tab stands for, in snippet indentation and for the Tab key.
+pub const TAB_WIDTH: usize = 4;
+
+/// Splits a snippet into words for the input processor. The first word of a
+/// line carries its indentation (tabs expanded to spaces) and the last one
+/// the line break, with blank lines folded into the break before them.
+pub fn snippet_words(code: &str) -> Vec<String> {
+    let mut words: Vec<String> = Vec::new();
+    for line in code.lines() {
+        let line = line.trim_end();
+        if line.is_empty() {
+            if let Some(last) = words.last_mut() {
+                last.push('\n');
+            }
+            continue;
+        }
+        let body = line.trim_start();
+        let indent = expand_tabs(&line[..line.len() - body.len()]);
+        let start = words.len();
+        words.extend(body.split_whitespace().map(str::to_string));
+        words[start].insert_str(0, &indent);
+        if let Some(last) = words.last_mut() {
+            last.push('\n');
+        }
+    }
+    // The snippet ends on its last word, not on a line break
+    if let Some(last) = words.last_mut() {
+        let end = last.trim_end_matches('\n').len();
+        last.truncate(end);
+    }
+    words
+}
+
+/// Indentation with tabs expanded to the next tab stop.
+pub(crate) fn expand_tabs(indent: &str) -> String {
+    let mut out = String::new();
+    for c in indent.chars() {
+        if c == '\t' {
+            let pad = TAB_WIDTH - out.len() % TAB_WIDTH;
+            out.push_str(&" ".repeat(pad));
+        } else {
+            out.push(' ');
+        }
+    }
+    out
+}
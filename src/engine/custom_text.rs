@@ -0,0 +1,23 @@
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+/// Splits custom text into target words on any whitespace (spaces, tabs,
+/// newlines), keeping punctuation and case as written.
+pub fn tokenize(text: &str) -> Vec<String> {
+    text.split_whitespace().map(String::from).collect()
+}
+
+/// Builds the word list for a custom text test: the text repeated `repeat`
+/// times (at least once), optionally shuffled. Shuffling is driven by `seed`
+/// so a replayed test gets the same order.
+pub fn build_custom_words(text: &str, shuffle: bool, repeat: u32, seed: u64) -> Vec<String> {
+    let tokens = tokenize(text);
+    let mut words: Vec<String> = (0..repeat.max(1))
+        .flat_map(|_| tokens.iter().cloned())
+        .collect();
+    if shuffle {
+        words.shuffle(&mut StdRng::seed_from_u64(seed));
+    }
+    words
+}
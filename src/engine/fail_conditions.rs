@@ -0,0 +1,36 @@
+use crate::types::{FailReason, InputState, StatsSnapshot, TestConfig};
+
+/// Seconds before the minimum wpm and accuracy thresholds are enforced, so a
+/// slow first word or an early typo doesn't end the test instantly.
+pub const FAIL_GRACE_SECONDS: f64 = 5.0;
+
+/// Checks the configured fail conditions against the live test.
+/// Sudden death applies from the first keystroke; the thresholds only after
+/// the grace period.
+pub fn check_failure(
+    config: &TestConfig,
+    state: &InputState,
+    stats: &StatsSnapshot,
+    elapsed_seconds: f64,
+) -> Option<FailReason> {
+    if config.sudden_death && state.keypress_incorrect > 0 {
+        return Some(FailReason::SuddenDeath);
+    }
+
+    if elapsed_seconds < FAIL_GRACE_SECONDS {
+        return None;
+    }
+
+    if let Some(min) = config.min_accuracy {
+        let typed = state.keypress_correct + state.keypress_incorrect;
+        if typed > 0 && stats.accuracy < min as f64 {
+            return Some(FailReason::MinAccuracy);
+        }
+    }
+
+    if config.min_wpm.is_some_and(|min| stats.wpm < min as f64) {
+        return Some(FailReason::MinWpm);
+    }
+
+    None
+}
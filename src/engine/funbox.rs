@@ -0,0 +1,99 @@
+use crate::engine::graphemes::graphemes;
+use crate::types::Funbox;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Letters used by gibberish when the words offer none (e.g. all numbers).
+const FALLBACK_LETTERS: &str = "abcdefghijklmnopqrstuvwxyz";
+
+/// Rewrites generated words for a funbox modifier. Random funboxes are
+/// driven by `seed` so a replayed test gets the same text. `Nospace` leaves
+/// the words alone; it is enforced by the input processor instead.
+pub fn apply_funbox(words: &[String], funbox: Funbox, seed: u64) -> Vec<String> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    match funbox {
+        Funbox::Mirror => words.iter().map(|w| graphemes(w).into_iter().rev().collect()).collect(),
+        Funbox::RandomCase => words.iter().map(|w| random_case(w, &mut rng)).collect(),
+        Funbox::Capitals => words.iter().map(|w| capitalize(w)).collect(),
+        Funbox::Gibberish => {
+            let letters = letters_of(words);
+            words
+                .iter()
+                .map(|_| {
+                    let len = rng.random_range(1..=7);
+                    (0..len)
+                        .map(|_| letters[rng.random_range(0..letters.len())])
+                        .collect()
+                })
+                .collect()
+        }
+        Funbox::Numbers => words
+            .iter()
+            .map(|w| {
+                let len = w.chars().count().clamp(1, 7);
+                (0..len)
+                    .map(|_| char::from(b'0' + rng.random_range(0..10u8)))
+                    .collect()
+            })
+            .collect(),
+        Funbox::LayoutMirror => words
+            .iter()
+            .map(|w| w.chars().map(layout_mirror).collect())
+            .collect(),
+        Funbox::Nospace => words.to_vec(),
+    }
+}
+
+fn random_case(word: &str, rng: &mut impl Rng) -> String {
+    word.chars()
+        .map(|c| {
+            if rng.random::<bool>() {
+                c.to_uppercase().to_string()
+            } else {
+                c.to_lowercase().to_string()
+            }
+        })
+        .collect()
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().to_string() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+/// Distinct lowercase letters of the words, so gibberish stays in the
+/// language's alphabet.
+fn letters_of(words: &[String]) -> Vec<char> {
+    let mut letters: Vec<char> = words
+        .iter()
+        .flat_map(|w| w.chars())
+        .filter(|c| c.is_alphabetic() && c.is_lowercase())
+        .collect();
+    letters.sort_unstable();
+    letters.dedup();
+    if letters.is_empty() {
+        letters = FALLBACK_LETTERS.chars().collect();
+    }
+    letters
+}
+
+/// The letter in the mirrored position of the same QWERTY row, e.g. `q` ↔ `p`
+/// and `a` ↔ `l`. Other characters are kept; case is preserved.
+fn layout_mirror(c: char) -> char {
+    const ROWS: [&str; 3] = ["qwertyuiop", "asdfghjkl", "zxcvbnm"];
+    let lower = c.to_ascii_lowercase();
+    for row in ROWS {
+        if let Some(i) = row.find(lower) {
+            let mirrored = row.as_bytes()[row.len() - 1 - i] as char;
+            return if c.is_ascii_uppercase() {
+                mirrored.to_ascii_uppercase()
+            } else {
+                mirrored
+            };
+        }
+    }
+    c
+}
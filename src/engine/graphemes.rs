@@ -0,0 +1,33 @@
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Splits text into user-perceived characters (extended grapheme clusters).
+pub fn graphemes(s: &str) -> Vec<&str> {
+    s.graphemes(true).collect()
+}
+
+pub fn grapheme_count(s: &str) -> usize {
+    s.graphemes(true).count()
+}
+
+/// Removes the last grapheme cluster, returning whether anything was removed.
+pub fn pop_grapheme(s: &mut String) -> bool {
+    match s.grapheme_indices(true).next_back() {
+        Some((idx, _)) => {
+            s.truncate(idx);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Terminal columns occupied by a single grapheme. Zero-width clusters still
+/// take one cell so the cursor never disappears.
+pub fn grapheme_width(g: &str) -> usize {
+    g.width().max(1)
+}
+
+/// Terminal columns occupied by a string, measured per grapheme.
+pub fn display_width(s: &str) -> usize {
+    s.graphemes(true).map(grapheme_width).sum()
+}
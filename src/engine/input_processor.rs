@@ -1,47 +1,161 @@
-use crate::types::{CharResult, InputState, WordState};
+use crate::engine::code_snippet::TAB_WIDTH;
+use crate::engine::graphemes::{grapheme_count, graphemes, pop_grapheme};
+use crate::engine::lazy_mode::lazy_equivalent;
+use crate::types::{
+    CharResult, Confidence, InputOptions, InputState, KeystrokeEvent, KeystrokeKind, StopOnError, WordState,
+};
 
+#[allow(dead_code)] // used by tests
 pub fn create_input_state(words: &[String]) -> InputState {
+    create_input_state_with_options(words, InputOptions::default())
+}
+
+pub fn create_input_state_with_options(words: &[String], options: InputOptions) -> InputState {
     InputState {
-        words: words
-            .iter()
-            .map(|w| WordState {
-                word: w.clone(),
-                typed: String::new(),
-                chars: Vec::new(),
-                completed: false,
-            })
-            .collect(),
+        words: words.iter().map(|w| new_word_state(w, &options)).collect(),
         current_word_index: 0,
         cursor_position: 0,
         finished: false,
         historical_error_chars: 0,
         keypress_correct: 0,
         keypress_incorrect: 0,
+        rejected_chars: 0,
+        keystrokes: Vec::new(),
+        options,
     }
 }
 
-pub fn process_char(mut state: InputState, ch: char) -> InputState {
+/// Appends more target words, e.g. to keep a time-mode test from running dry.
+pub fn append_words(mut state: InputState, words: &[String]) -> InputState {
+    let new_words: Vec<WordState> =
+        words.iter().map(|w| new_word_state(w, &state.options)).collect();
+    state.words.extend(new_words);
+    state
+}
+
+/// Code words bring their layout along: trailing newlines become the word's
+/// line breaks, and leading indentation is set aside when it is skipped.
+fn new_word_state(word: &str, options: &InputOptions) -> WordState {
+    let body = word.trim_end_matches('\n');
+    let line_breaks = word.len() - body.len();
+    let text = if options.skip_indent {
+        body.trim_start_matches(' ')
+    } else {
+        body
+    };
+    WordState {
+        word: text.to_string(),
+        typed: String::new(),
+        chars: Vec::new(),
+        completed: false,
+        started_ms: None,
+        completed_ms: None,
+        line_breaks,
+        indent: body[..body.len() - text.len()].to_string(),
+    }
+}
+
+pub fn process_char(state: InputState, ch: char) -> InputState {
     if state.finished {
         return state;
     }
 
+    let mut state = judge_char(state, ch);
+
+    // Nospace: a correctly typed word moves on by itself
+    let idx = state.current_word_index;
+    if state.options.nospace && state.words[idx].typed == state.words[idx].word {
+        state = complete_word(state);
+    }
+    state
+}
+
+fn judge_char(mut state: InputState, ch: char) -> InputState {
     let idx = state.current_word_index;
+    let lazy = state.options.lazy_mode;
+    let accents = &state.options.additional_accents;
     let current = &mut state.words[idx];
-    let pos = current.typed.len();
-    let expected = current.word.chars().nth(pos).unwrap_or('\0');
-    let is_extra = pos >= current.word.len();
 
-    let correct = !is_extra && ch == expected;
+    // Open-ended: the typed text is the target, so every keystroke matches
+    if state.options.open_ended {
+        current.word.push(ch);
+    }
+
+    // Lazy mode: a keystroke that completes a multi-letter spelling (e.g. the
+    // second "s" of "ss" for "ß") turns the pending letter into the target
+    if lazy && complete_lazy_spelling(current, ch, accents) {
+        state.keypress_correct += 1;
+        return state;
+    }
+    let pending_before = lazy && has_pending_lazy_spelling(current, accents);
+
+    let before = grapheme_count(&current.typed);
+    current.typed.push(ch);
+
+    // Positions are grapheme clusters, not bytes or chars, so accented and
+    // combined characters line up with the target word
+    let typed = graphemes(&current.typed);
+    let pos = typed.len() - 1;
+    let merged = typed.len() == before;
+    let abandoned_lazy = pending_before && !merged;
+    let typed_grapheme = typed[pos].to_string();
+    let expected = graphemes(&current.word).get(pos).map(|g| g.to_string());
+    let is_extra = expected.is_none();
+
+    let mut correct = !is_extra && expected.as_deref() == Some(typed_grapheme.as_str());
+
+    // Lazy mode: accept the unaccented spelling, or its first letter while the
+    // rest of a multi-letter spelling is still to come
+    let lazy_spelling = match &expected {
+        Some(e) if lazy && !correct => lazy_equivalent(e, accents),
+        _ => None,
+    };
+    let lazy_exact = lazy_spelling.as_deref() == Some(typed_grapheme.as_str());
+    let lazy_pending = lazy_spelling
+        .as_deref()
+        .is_some_and(|l| l.len() > typed_grapheme.len() && l.starts_with(&typed_grapheme));
+    correct |= lazy_exact || lazy_pending;
+
+    // Stop on letter: count the mistake but leave the word untouched. A
+    // partial cluster (e.g. the base of a decomposed accent) is let through.
+    let partial = expected
+        .as_deref()
+        .is_some_and(|e| e.starts_with(&typed_grapheme));
+    let rejected = (!correct && !partial) || abandoned_lazy;
+    if rejected && state.options.stop_on_error == StopOnError::Letter {
+        let keep = current.typed.len() - ch.len_utf8();
+        current.typed.truncate(keep);
+        state.historical_error_chars += 1;
+        state.keypress_incorrect += 1;
+        state.rejected_chars += 1;
+        return state;
+    }
+
+    if lazy_exact {
+        // Store the target grapheme so whole-word comparisons just work
+        if let Some(e) = &expected {
+            pop_grapheme(&mut current.typed);
+            current.typed.push_str(e);
+        }
+    }
+    if abandoned_lazy {
+        // The previous letter began a lazy spelling that was never finished
+        current.chars[pos - 1].correct = false;
+    }
 
     let result = CharResult {
         correct,
         extra: is_extra,
     };
 
-    current.typed.push(ch);
-    current.chars.push(result);
+    if !merged {
+        current.chars.push(result);
+    } else {
+        // A combining mark merged into the previous cluster: re-judge it
+        current.chars[pos] = result;
+    }
 
-    state.cursor_position = current.typed.len();
+    state.cursor_position = pos + 1;
     if !correct {
         state.historical_error_chars += 1;
     }
@@ -54,11 +168,58 @@ pub fn process_char(mut state: InputState, ch: char) -> InputState {
     state
 }
 
+/// Whether the last typed grapheme is the start of a multi-letter lazy
+/// spelling of its target, e.g. "s" towards "ss" for "ß".
+fn has_pending_lazy_spelling(word: &WordState, accents: &[(String, String)]) -> bool {
+    let typed = graphemes(&word.typed);
+    let Some(last) = typed.last() else {
+        return false;
+    };
+    let Some(expected) = graphemes(&word.word).get(typed.len() - 1).copied() else {
+        return false;
+    };
+    if *last == expected {
+        return false;
+    }
+    lazy_equivalent(expected, accents).is_some_and(|l| l.len() > last.len() && l.starts_with(last))
+}
+
+fn complete_lazy_spelling(word: &mut WordState, ch: char, accents: &[(String, String)]) -> bool {
+    if !has_pending_lazy_spelling(word, accents) {
+        return false;
+    }
+    let pos = grapheme_count(&word.typed) - 1;
+    let expected = graphemes(&word.word)[pos].to_string();
+    let mut candidate = graphemes(&word.typed)[pos].to_string();
+    candidate.push(ch);
+    if lazy_equivalent(&expected, accents).as_deref() != Some(candidate.as_str()) {
+        return false;
+    }
+    pop_grapheme(&mut word.typed);
+    word.typed.push_str(&expected);
+    word.chars[pos].correct = true;
+    true
+}
+
 pub fn process_space(mut state: InputState) -> InputState {
     if state.finished {
         return state;
     }
 
+    // Nospace: words complete on their own, a space is never part of the
+    // text. It only moves past a word with mistakes in it.
+    if state.options.nospace {
+        let word = &state.words[state.current_word_index];
+        if word.chars.iter().all(|cr| cr.correct && !cr.extra) {
+            return state;
+        }
+    }
+
+    // Indentation that isn't skipped is typed space by space
+    if expects_indent(&state) {
+        return judge_char(state, ' ');
+    }
+
     let idx = state.current_word_index;
 
     // Don't advance if nothing typed in current word
@@ -66,15 +227,62 @@ pub fn process_space(mut state: InputState) -> InputState {
         return state;
     }
 
-    let space_correct = state.words[idx].typed == state.words[idx].word;
-    state.words[idx].completed = true;
+    // The last word on a line is left with Enter
+    if state.words[idx].line_breaks > 0 {
+        state.keypress_incorrect += 1;
+        return state;
+    }
 
-    let next_index = idx + 1;
-    let finished = next_index >= state.words.len();
+    separate_word(state)
+}
 
-    state.current_word_index = if finished { idx } else { next_index };
-    state.cursor_position = 0;
-    state.finished = finished;
+/// Enter ends a line of code. Anywhere else it is not part of the text.
+pub fn process_enter(state: InputState) -> InputState {
+    if state.finished {
+        return state;
+    }
+    let current = &state.words[state.current_word_index];
+    if current.line_breaks == 0 || current.typed.is_empty() {
+        return state;
+    }
+    separate_word(state)
+}
+
+/// Tab types the expected indentation up to the next tab stop.
+pub fn process_tab(mut state: InputState) -> InputState {
+    if state.finished {
+        return state;
+    }
+    let column = grapheme_count(&state.words[state.current_word_index].typed);
+    for _ in 0..TAB_WIDTH - column % TAB_WIDTH {
+        if !expects_indent(&state) {
+            break;
+        }
+        state = judge_char(state, ' ');
+    }
+    state
+}
+
+/// Whether the cursor sits in indentation that has to be typed, i.e. code
+/// with auto-skipped indentation turned off.
+pub fn expects_indent(state: &InputState) -> bool {
+    let current = &state.words[state.current_word_index];
+    graphemes(&current.word)
+        .get(grapheme_count(&current.typed))
+        .is_some_and(|g| *g == " ")
+}
+
+/// Leaves the current word with a space or Enter, judging the whole word.
+fn separate_word(mut state: InputState) -> InputState {
+    let idx = state.current_word_index;
+    let space_correct = state.words[idx].typed == state.words[idx].word;
+
+    // Stop on error: refuse to leave a word until it's typed correctly
+    if !space_correct && state.options.stop_on_error != StopOnError::Off {
+        state.keypress_incorrect += 1;
+        return state;
+    }
+    state = complete_word(state);
 
     if space_correct {
         state.keypress_correct += 1;
@@ -85,61 +293,177 @@ pub fn process_space(mut state: InputState) -> InputState {
     state
 }
 
+/// Marks the current word completed and moves on to the next one, finishing
+/// the test after the last word.
+fn complete_word(mut state: InputState) -> InputState {
+    let idx = state.current_word_index;
+    state.words[idx].completed = true;
+
+    let next_index = idx + 1;
+    if state.options.open_ended && next_index == state.words.len() {
+        state.words.push(new_word_state("", &state.options));
+    }
+    let finished = next_index >= state.words.len();
+
+    state.current_word_index = if finished { idx } else { next_index };
+    state.cursor_position = 0;
+    state.finished = finished;
+    state
+}
+
 pub fn process_backspace(mut state: InputState) -> InputState {
     if state.finished {
         return state;
     }
 
+    if state.options.confidence == Confidence::Max {
+        return state;
+    }
+
     let idx = state.current_word_index;
 
-    if !state.words[idx].typed.is_empty() {
+    if pop_grapheme(&mut state.words[idx].typed) {
         // Delete last character in current word
-        state.words[idx].typed.pop();
         state.words[idx].chars.pop();
-        state.cursor_position = state.words[idx].typed.len();
+        if state.options.open_ended {
+            pop_grapheme(&mut state.words[idx].word);
+        }
+        state.cursor_position = grapheme_count(&state.words[idx].typed);
         return state;
     }
 
-    // If at start of word and not the first word, go back to previous word
-    if idx > 0 && state.words[idx - 1].completed {
+    // If at start of word and not the first word, go back to previous word.
+    // Confidence keeps completed words locked, and correct words can be
+    // locked on their own.
+    if state.options.confidence != Confidence::Off || idx == 0 {
+        return state;
+    }
+    let prev = &state.words[idx - 1];
+    let reopenable = !state.options.lock_correct_words || prev.typed != prev.word;
+    if prev.completed && reopenable {
         state.words[idx - 1].completed = false;
         state.current_word_index = idx - 1;
-        state.cursor_position = state.words[idx - 1].typed.len();
+        state.cursor_position = grapheme_count(&state.words[idx - 1].typed);
     }
 
     state
 }
 
 pub fn process_ctrl_backspace(mut state: InputState) -> InputState {
-    if state.finished {
+    if state.finished || state.options.confidence == Confidence::Max {
         return state;
     }
 
     let idx = state.current_word_index;
     state.words[idx].typed.clear();
     state.words[idx].chars.clear();
+    if state.options.open_ended {
+        state.words[idx].word.clear();
+    }
     state.cursor_position = 0;
 
     state
 }
 
-pub fn process_keystroke(state: InputState, input: &str, backspace: bool, ctrl: bool) -> InputState {
-    if backspace {
+/// Ends an open-ended (zen) test. The word being typed counts as completed,
+/// as if a space had been typed after it.
+pub fn finish_input(mut state: InputState, elapsed_ms: f64) -> InputState {
+    if state.finished {
+        return state;
+    }
+    let idx = state.current_word_index;
+    let current = &mut state.words[idx];
+    if !current.typed.is_empty() && !current.completed {
+        current.completed = true;
+        current.completed_ms = Some(elapsed_ms);
+    }
+    state.finished = true;
+    state
+}
+
+/// Applies a raw keystroke and appends it to the state's keystroke log.
+/// `elapsed_ms` is the time since the test timer was started.
+pub fn process_keystroke(
+    mut state: InputState,
+    input: &str,
+    backspace: bool,
+    ctrl: bool,
+    elapsed_ms: f64,
+) -> InputState {
+    let kind = if backspace {
         if ctrl {
-            return process_ctrl_backspace(state);
+            KeystrokeKind::CtrlBackspace
+        } else {
+            KeystrokeKind::Backspace
         }
-        return process_backspace(state);
+    } else if input == " " {
+        KeystrokeKind::Space
+    } else if input == "\n" {
+        KeystrokeKind::Enter
+    } else if input == "\t" {
+        KeystrokeKind::Tab
+    } else {
+        // Only process printable characters (single chars)
+        let chars: Vec<char> = input.chars().collect();
+        if chars.len() == 1 && chars[0] >= ' ' {
+            KeystrokeKind::Char(chars[0])
+        } else {
+            return state;
+        }
+    };
+
+    if state.finished {
+        return state;
     }
 
-    if input == " " {
-        return process_space(state);
+    let word_index = state.current_word_index;
+    let char_index = state.cursor_position;
+    let correct_before = state.keypress_correct;
+    let incorrect_before = state.keypress_incorrect;
+
+    state.words[word_index].started_ms.get_or_insert(elapsed_ms);
+
+    state = match kind {
+        KeystrokeKind::Char(ch) => process_char(state, ch),
+        KeystrokeKind::Space => process_space(state),
+        KeystrokeKind::Enter => process_enter(state),
+        KeystrokeKind::Tab => process_tab(state),
+        KeystrokeKind::Backspace => process_backspace(state),
+        KeystrokeKind::CtrlBackspace => process_ctrl_backspace(state),
+    };
+
+    // A keystroke that moved on (or ended the test) completes the word and
+    // starts the next one's clock. Usually a space or Enter; in nospace, the
+    // last letter.
+    let advanced = state.current_word_index > word_index || state.finished;
+    if advanced {
+        state.words[word_index].completed_ms = Some(elapsed_ms);
+        if let Some(next) = state.words.get_mut(word_index + 1) {
+            next.started_ms.get_or_insert(elapsed_ms);
+        }
     }
 
-    // Only process printable characters (single chars)
-    let chars: Vec<char> = input.chars().collect();
-    if chars.len() == 1 && chars[0] >= ' ' {
-        return process_char(state, chars[0]);
+    // Keystrokes that didn't touch the keypress counters (deletions, a
+    // space on an empty word) are logged without a verdict
+    let correct = if state.keypress_correct > correct_before {
+        Some(true)
+    } else if state.keypress_incorrect > incorrect_before {
+        Some(false)
+    } else {
+        None
+    };
+    // A space nospace ignored was never part of the test
+    if state.options.nospace && kind == KeystrokeKind::Space && !advanced && correct.is_none() {
+        return state;
     }
 
+    state.keystrokes.push(KeystrokeEvent {
+        timestamp_ms: elapsed_ms,
+        kind,
+        word_index,
+        char_index,
+        correct,
+    });
+
     state
 }
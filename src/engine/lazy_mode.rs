@@ -0,0 +1,61 @@
+/// Plain-ASCII spelling accepted for an accented grapheme in lazy mode, e.g.
+/// `e` for `é` or `ss` for `ß`. Language-specific `additional` accents (such
+/// as German `ä` → `ae`) take precedence over the built-in table.
+pub fn lazy_equivalent(grapheme: &str, additional: &[(String, String)]) -> Option<String> {
+    let lower = grapheme.to_lowercase();
+    let is_upper = lower != grapheme;
+
+    let folded = additional
+        .iter()
+        .find(|(accent, _)| *accent == lower)
+        .map(|(_, replacement)| replacement.clone())
+        .or_else(|| fold_grapheme(&lower))?;
+
+    if !is_upper {
+        return Some(folded);
+    }
+    let mut chars = folded.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().to_string() + chars.as_str())
+}
+
+fn fold_grapheme(grapheme: &str) -> Option<String> {
+    let mut chars = grapheme.chars();
+    let first = chars.next()?;
+    let rest = chars.as_str();
+
+    // Decomposed form: base letter followed only by combining diacritics
+    if !rest.is_empty() {
+        let all_marks = rest.chars().all(|c| ('\u{300}'..='\u{36f}').contains(&c));
+        return all_marks.then(|| fold_char(first).unwrap_or(first).to_string());
+    }
+
+    match first {
+        'æ' => Some("ae".into()),
+        'œ' => Some("oe".into()),
+        'ß' => Some("ss".into()),
+        _ => fold_char(first).map(String::from),
+    }
+}
+
+fn fold_char(c: char) -> Option<char> {
+    let base = match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' | 'ą' => 'a',
+        'ç' | 'ć' | 'č' => 'c',
+        'ď' | 'đ' => 'd',
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ę' | 'ě' => 'e',
+        'ì' | 'í' | 'î' | 'ï' | 'ī' => 'i',
+        'ł' => 'l',
+        'ñ' | 'ń' | 'ň' => 'n',
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' | 'ő' => 'o',
+        'ř' => 'r',
+        'ś' | 'š' => 's',
+        'ť' => 't',
+        'ù' | 'ú' | 'û' | 'ü' | 'ū' | 'ů' | 'ű' => 'u',
+        'ý' | 'ÿ' => 'y',
+        'ź' | 'ż' | 'ž' => 'z',
+        _ => return None,
+    };
+    Some(base)
+}
@@ -1,4 +1,13 @@
+pub mod adaptive;
+pub mod code_snippet;
+pub mod custom_text;
+pub mod fail_conditions;
+pub mod funbox;
+pub mod graphemes;
 pub mod input_processor;
+pub mod lazy_mode;
+pub mod source_code;
 pub mod stats_calculator;
 pub mod timer;
+pub mod word_filter;
 pub mod word_generator;
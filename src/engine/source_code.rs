@@ -0,0 +1,250 @@
+use crate::engine::code_snippet::expand_tabs;
+use crate::types::ExcerptKind;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::ops::Range;
+use std::path::Path;
+
+/// Longest function worth typing in one test, in lines.
+pub const MAX_EXCERPT_LINES: usize = 24;
+/// Lines typed when practicing on a line range.
+pub const LINE_RANGE_LINES: usize = 10;
+
+/// Code language of a source file, named like the bundled word lists.
+pub fn detect_language(path: &Path) -> Option<&'static str> {
+    let extension = path.extension()?.to_str()?;
+    match extension {
+        "rs" => Some("code_rust"),
+        "py" => Some("code_python"),
+        "go" => Some("code_go"),
+        "js" | "mjs" | "cjs" | "jsx" => Some("code_javascript"),
+        "ts" | "mts" | "cts" | "tsx" => Some("code_typescript"),
+        _ => None,
+    }
+}
+
+/// Cuts a function or a run of lines out of `code`, chosen by `seed`.
+/// Returns the dedented text and its 0-based line range, or `None` if the
+/// file has nothing but blank lines.
+pub fn extract_excerpt(
+    code: &str,
+    language: &str,
+    kind: ExcerptKind,
+    seed: u64,
+) -> Option<(String, Range<usize>)> {
+    let lines: Vec<&str> = code.lines().collect();
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let functions: Vec<Range<usize>> = match kind {
+        ExcerptKind::Functions => find_functions(&lines, language)
+            .into_iter()
+            .filter(|f| f.len() <= MAX_EXCERPT_LINES)
+            .collect(),
+        ExcerptKind::Lines => Vec::new(),
+    };
+    let range = if functions.is_empty() {
+        let starts: Vec<usize> = (0..lines.len())
+            .filter(|&i| !lines[i].trim().is_empty())
+            .collect();
+        if starts.is_empty() {
+            return None;
+        }
+        let start = starts[rng.random_range(0..starts.len())];
+        start..(start + LINE_RANGE_LINES).min(lines.len())
+    } else {
+        functions[rng.random_range(0..functions.len())].clone()
+    };
+
+    // Trailing blank lines add nothing to type
+    let mut range = range;
+    while range.len() > 1 && lines[range.end - 1].trim().is_empty() {
+        range.end -= 1;
+    }
+    Some((dedent(&lines[range.clone()]), range))
+}
+
+/// Line ranges of the functions in a file, from signature to closing line.
+pub fn find_functions(lines: &[&str], language: &str) -> Vec<Range<usize>> {
+    let mut functions = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        if !starts_function(line.trim_start(), language) {
+            continue;
+        }
+        let end = if language == "code_python" {
+            indented_block_end(lines, i)
+        } else {
+            brace_block_end(lines, i)
+        };
+        if let Some(end) = end {
+            functions.push(i..end);
+        }
+    }
+    functions
+}
+
+fn starts_function(line: &str, language: &str) -> bool {
+    match language {
+        "code_rust" => {
+            const MODIFIERS: &[&str] =
+                &["pub", "pub(crate)", "pub(super)", "async", "const", "unsafe", "extern", "\"C\""];
+            let mut tokens = line.split_whitespace().skip_while(|t| MODIFIERS.contains(t));
+            tokens.next() == Some("fn")
+        }
+        "code_go" => line.starts_with("func "),
+        "code_python" => line.starts_with("def ") || line.starts_with("async def "),
+        "code_javascript" | "code_typescript" => {
+            let line = line
+                .trim_start_matches("export ")
+                .trim_start_matches("default ")
+                .trim_start_matches("async ");
+            line.starts_with("function")
+                || ((line.starts_with("const ") || line.starts_with("let "))
+                    && line.trim_end().ends_with("=> {"))
+        }
+        _ => false,
+    }
+}
+
+/// One past the line whose `}` closes the block opened on or after `start`.
+/// Declarations without a body (`fn f();`) have no block.
+fn brace_block_end(lines: &[&str], start: usize) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut opened = false;
+    for (i, line) in lines.iter().enumerate().skip(start).take(MAX_EXCERPT_LINES * 4) {
+        for c in line.chars() {
+            match c {
+                '{' => {
+                    depth += 1;
+                    opened = true;
+                }
+                '}' => depth = depth.saturating_sub(1),
+                ';' if !opened => return None,
+                _ => {}
+            }
+        }
+        if opened && depth == 0 {
+            return Some(i + 1);
+        }
+    }
+    None
+}
+
+/// One past the last line indented deeper than the `def` on `start`.
+fn indented_block_end(lines: &[&str], start: usize) -> Option<usize> {
+    let indent = indent_width(lines[start]);
+    let mut end = start + 1;
+    for (i, line) in lines.iter().enumerate().skip(start + 1) {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if indent_width(line) <= indent {
+            break;
+        }
+        end = i + 1;
+    }
+    (end > start + 1).then_some(end)
+}
+
+fn indent_width(line: &str) -> usize {
+    let body = line.trim_start();
+    expand_tabs(&line[..line.len() - body.len()]).len()
+}
+
+/// Expands tabs and removes the indentation all lines share.
+fn dedent(lines: &[&str]) -> String {
+    let expanded: Vec<String> = lines
+        .iter()
+        .map(|line| {
+            let body = line.trim_start();
+            expand_tabs(&line[..line.len() - body.len()]) + body.trim_end()
+        })
+        .collect();
+    let common = expanded
+        .iter()
+        .filter(|l| !l.is_empty())
+        .map(|l| l.len() - l.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    expanded
+        .iter()
+        .map(|l| l.get(common..).unwrap_or(""))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Removes line and block comments, leaving strings alone. Lines that held
+/// nothing but a comment are dropped; blank lines are kept.
+pub fn strip_comments(code: &str, language: &str) -> String {
+    // Marks where a comment was cut, to tell emptied lines from blank ones
+    const CUT: char = '\u{0}';
+    let python = language == "code_python";
+    // Rust and Go use `'` for char literals (and Rust for lifetimes too)
+    let char_literals = matches!(language, "code_rust" | "code_go");
+
+    let chars: Vec<char> = code.chars().collect();
+    let mut out = String::with_capacity(code.len());
+    let mut quote: Option<char> = None;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        if let Some(q) = quote {
+            out.push(c);
+            if c == '\\' {
+                if let Some(n) = next {
+                    out.push(n);
+                    i += 1;
+                }
+            } else if c == q {
+                quote = None;
+            }
+            i += 1;
+            continue;
+        }
+        let line_comment = if python {
+            c == '#'
+        } else {
+            c == '/' && next == Some('/')
+        };
+        if line_comment {
+            while i < chars.len() && chars[i] != '\n' {
+                i += 1;
+            }
+            out.push(CUT);
+            continue;
+        }
+        if !python && c == '/' && next == Some('*') {
+            i += 2;
+            while i < chars.len() && !(chars[i] == '*' && chars.get(i + 1) == Some(&'/')) {
+                i += 1;
+            }
+            i += 2;
+            out.push(CUT);
+            continue;
+        }
+        match c {
+            '\'' if char_literals => {
+                // 'x' and '\n' are literals; anything else is a lifetime
+                let literal_end = match next {
+                    Some('\\') => (i + 2..chars.len().min(i + 12)).find(|&j| chars[j] == '\''),
+                    Some(_) if chars.get(i + 2) == Some(&'\'') => Some(i + 2),
+                    _ => None,
+                };
+                let end = literal_end.unwrap_or(i);
+                out.extend(&chars[i..=end]);
+                i = end + 1;
+                continue;
+            }
+            '"' | '`' | '\'' => quote = Some(c),
+            _ => {}
+        }
+        out.push(c);
+        i += 1;
+    }
+
+    out.lines()
+        .filter(|line| !(line.contains(CUT) && line.replace(CUT, "").trim().is_empty()))
+        .map(|line| line.replace(CUT, "").trim_end().to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
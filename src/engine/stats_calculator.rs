@@ -1,6 +1,18 @@
-use crate::types::{InputState, StatsSnapshot};
+use crate::engine::graphemes::{grapheme_count, graphemes};
+use crate::types::{InputState, KeyStats, KeystrokeKind, StatsSnapshot, WordTiming};
+use std::collections::BTreeMap;
 
 pub fn calculate_stats(state: &InputState, elapsed_seconds: f64) -> StatsSnapshot {
+    StatsSnapshot {
+        key_stats: calculate_key_stats(state),
+        bigram_stats: calculate_bigram_stats(state),
+        ..calculate_live_stats(state, elapsed_seconds)
+    }
+}
+
+/// Speed and accuracy without the per-key and bigram breakdowns, which walk
+/// the whole keystroke log; cheap enough to run on every keystroke.
+pub fn calculate_live_stats(state: &InputState, elapsed_seconds: f64) -> StatsSnapshot {
     if elapsed_seconds <= 0.0 {
         return StatsSnapshot::default();
     }
@@ -27,21 +39,29 @@ pub fn calculate_stats(state: &InputState, elapsed_seconds: f64) -> StatsSnapsho
         }
 
         // Count missed characters (word was completed but not fully typed)
-        if word_state.completed && word_state.typed.len() < word_state.word.len() {
-            missed_chars += word_state.word.len() - word_state.typed.len();
+        let word_len = grapheme_count(&word_state.word);
+        let typed_len = grapheme_count(&word_state.typed);
+        if word_state.completed && typed_len < word_len {
+            missed_chars += word_len - typed_len;
         }
 
         if word_state.completed {
-            spaces += 1;
+            // Nospace words are completed without typing a space
+            let space = usize::from(!state.options.nospace);
+            spaces += space;
 
             // WPM: only count chars from entirely correct words (MonkeyType formula)
             if word_state.typed == word_state.word {
-                correct_word_chars += word_state.word.len();
-                correct_spaces += 1;
+                correct_word_chars += word_len;
+                correct_spaces += space;
             }
         }
     }
 
+    // Stop-on-letter keeps wrong keystrokes out of the words, but they were
+    // still typed
+    incorrect_chars += state.rejected_chars;
+
     let minutes = elapsed_seconds / 60.0;
 
     // Net WPM: only correctly-typed whole words + their spaces
@@ -68,7 +88,88 @@ pub fn calculate_stats(state: &InputState, elapsed_seconds: f64) -> StatsSnapsho
         extra_chars,
         missed_chars,
         elapsed_seconds,
+        ..StatsSnapshot::default()
+    }
+}
+
+/// Per-character breakdown built from the keystroke log: hits, misses, what
+/// was typed instead, and how long each correct press took.
+pub fn calculate_key_stats(state: &InputState) -> BTreeMap<String, KeyStats> {
+    tally_keystrokes(state, |word, i| word.get(i).map(|g| g.to_string()))
+}
+
+/// Like [`calculate_key_stats`], keyed by the expected character together
+/// with the one before it in the word, e.g. `th`. The first letter of a word
+/// has no bigram.
+pub fn calculate_bigram_stats(state: &InputState) -> BTreeMap<String, KeyStats> {
+    tally_keystrokes(state, |word, i| {
+        let previous = word.get(i.checked_sub(1)?)?;
+        Some(format!("{previous}{}", word.get(i)?))
+    })
+}
+
+/// Attributes every character keystroke to the key `key_of` names for its
+/// position in the expected word. Extra characters have no expected key.
+fn tally_keystrokes(
+    state: &InputState,
+    key_of: impl Fn(&[&str], usize) -> Option<String>,
+) -> BTreeMap<String, KeyStats> {
+    let mut stats: BTreeMap<String, KeyStats> = BTreeMap::new();
+    let mut previous_ms: Option<f64> = None;
+
+    for event in &state.keystrokes {
+        let since_previous = previous_ms.map(|p| event.timestamp_ms - p);
+        previous_ms = Some(event.timestamp_ms);
+
+        let (KeystrokeKind::Char(ch), Some(correct)) = (event.kind, event.correct) else {
+            continue;
+        };
+        let Some(expected) = state
+            .words
+            .get(event.word_index)
+            .and_then(|w| key_of(&graphemes(&w.word), event.char_index))
+        else {
+            continue;
+        };
+
+        let entry = stats.entry(expected).or_default();
+        if correct {
+            entry.correct += 1;
+            if let Some(ms) = since_previous {
+                entry.timed_presses += 1;
+                entry.avg_press_ms += (ms - entry.avg_press_ms) / entry.timed_presses as f64;
+            }
+        } else {
+            entry.incorrect += 1;
+            *entry.substitutions.entry(ch.to_string()).or_default() += 1;
+        }
     }
+
+    stats
+}
+
+/// Burst speed for every completed word that has both timestamps.
+pub fn calculate_word_history(state: &InputState) -> Vec<WordTiming> {
+    state
+        .words
+        .iter()
+        .filter(|w| w.completed)
+        .filter_map(|w| {
+            let duration_ms = w.completed_ms? - w.started_ms?;
+            let burst_wpm = if duration_ms > 0.0 {
+                round_to_2(grapheme_count(&w.typed) as f64 / 5.0 / (duration_ms / 60_000.0))
+            } else {
+                0.0
+            };
+            Some(WordTiming {
+                word: w.word.clone(),
+                typed: w.typed.clone(),
+                correct: w.typed == w.word,
+                duration_ms,
+                burst_wpm,
+            })
+        })
+        .collect()
 }
 
 /// MonkeyType's "kogasa" consistency function.
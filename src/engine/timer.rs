@@ -53,3 +53,11 @@ pub fn get_remaining_seconds(state: &TimerState) -> f64 {
         Some(limit) => ((limit - state.elapsed_ms) / 1000.0).max(0.0),
     }
 }
+
+/// Milliseconds between the timer's start and `now`, independent of the last tick.
+pub fn get_elapsed_ms_at(state: &TimerState, now: Instant) -> f64 {
+    match state.start_time {
+        Some(start) => now.duration_since(start).as_secs_f64() * 1000.0,
+        None => 0.0,
+    }
+}
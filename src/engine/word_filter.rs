@@ -0,0 +1,110 @@
+use crate::engine::graphemes::grapheme_count;
+use crate::types::{KeySet, KeyboardLayout, Relaxation, WordFilter};
+use std::borrow::Cow;
+
+/// Fewer matching words than this make for a repetitive drill, so the
+/// filter is relaxed until at least this many are left.
+pub const MIN_FILTERED_WORDS: usize = 5;
+
+/// Top, home and bottom rows of each layout's letter block.
+fn layout_rows(layout: KeyboardLayout) -> [&'static str; 3] {
+    match layout {
+        KeyboardLayout::Qwerty => ["qwertyuiop", "asdfghjkl;'", "zxcvbnm,./"],
+        KeyboardLayout::Dvorak => ["',.pyfgcrl", "aoeuidhtns-", ";qjkxbmwvz"],
+        KeyboardLayout::Colemak => ["qwfpgjluy;", "arstdhneio'", "zxcvbkm,./"],
+    }
+}
+
+/// Characters on the keys of `set`. The left hand covers the first five
+/// columns of every row, the right hand the rest.
+pub fn key_set_chars(layout: KeyboardLayout, set: KeySet) -> String {
+    let [top, home, bottom] = layout_rows(layout);
+    let hand = |left: bool| {
+        [top, home, bottom]
+            .iter()
+            .flat_map(|row| {
+                let (l, r) = row.split_at(5);
+                (if left { l } else { r }).chars()
+            })
+            .collect()
+    };
+    match set {
+        KeySet::HomeRow => home.to_string(),
+        KeySet::TopRow => top.to_string(),
+        KeySet::BottomRow => bottom.to_string(),
+        KeySet::LeftHand => hand(true),
+        KeySet::RightHand => hand(false),
+    }
+}
+
+/// Whether `word` meets every constraint of `filter`.
+pub fn matches_filter(word: &str, filter: &WordFilter) -> bool {
+    let length = grapheme_count(word);
+    if filter.min_length.is_some_and(|min| length < min)
+        || filter.max_length.is_some_and(|max| length > max)
+    {
+        return false;
+    }
+    let lower = word.to_lowercase();
+    if !filter.required.is_empty()
+        && !filter.required.to_lowercase().chars().any(|c| lower.contains(c))
+    {
+        return false;
+    }
+    match filter.allowed {
+        Some(set) => {
+            let keys = key_set_chars(filter.layout, set);
+            lower.chars().all(|c| keys.contains(c))
+        }
+        None => true,
+    }
+}
+
+/// The words of `list` that pass `filter`, in list order so frequent words
+/// stay frequent. A rank window needs a `ranked` (frequency-ordered) list
+/// that reaches it. When fewer than [`MIN_FILTERED_WORDS`] pass, constraints
+/// are dropped one at a time (length, required characters, rank window, then
+/// the allowed keys) and reported in the order they were dropped.
+pub fn filter_words<'a>(
+    list: &'a [String],
+    ranked: bool,
+    filter: &WordFilter,
+) -> (Cow<'a, [String]>, Vec<Relaxation>) {
+    let mut active = filter.clone();
+    let mut relaxed = Vec::new();
+    if active.ranks.is_some_and(|(from, _)| !ranked || from >= list.len()) {
+        active.ranks = None;
+        relaxed.push(Relaxation::Ranks);
+    }
+    loop {
+        if active.is_empty() {
+            return (Cow::Borrowed(list), relaxed);
+        }
+        let pool = match active.ranks {
+            Some((from, to)) => &list[from..to.clamp(from, list.len())],
+            None => list,
+        };
+        let words: Vec<String> = pool
+            .iter()
+            .filter(|w| matches_filter(w, &active))
+            .cloned()
+            .collect();
+        if words.len() >= MIN_FILTERED_WORDS.min(list.len()) {
+            return (Cow::Owned(words), relaxed);
+        }
+        if active.min_length.is_some() || active.max_length.is_some() {
+            active.min_length = None;
+            active.max_length = None;
+            relaxed.push(Relaxation::Length);
+        } else if !active.required.is_empty() {
+            active.required.clear();
+            relaxed.push(Relaxation::Required);
+        } else if active.ranks.is_some() {
+            active.ranks = None;
+            relaxed.push(Relaxation::Ranks);
+        } else {
+            active.allowed = None;
+            relaxed.push(Relaxation::Allowed);
+        }
+    }
+}
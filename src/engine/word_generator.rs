@@ -1,62 +1,320 @@
-use crate::data::languages::get_language;
-use crate::types::GeneratorConfig;
-use rand::Rng;
+use crate::data::languages::{get_language, get_word_list};
+use crate::engine::word_filter::filter_words;
+use crate::types::{GeneratorConfig, PunctuationProfile, Relaxation};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::borrow::Cow;
+
+/// Upper bound for randomly picked seeds, kept short enough to read out and share.
+pub const MAX_RANDOM_SEED: u64 = 1_000_000_000;
+/// Share of words drawn for the adaptive targets, when there are any.
+const TARGETED_SHARE: f64 = 0.5;
+/// Draws tried for a word containing a target before settling for any word.
+const TARGETED_ATTEMPTS: usize = 30;
+
+pub fn random_seed() -> u64 {
+    rand::rng().random_range(0..MAX_RANDOM_SEED)
+}
+
+/// The words a config draws from.
+pub struct WordPool {
+    pub words: Cow<'static, [String]>,
+    /// Filter constraints dropped to leave enough words.
+    pub relaxed: Vec<Relaxation>,
+}
+
+/// The chosen list size of the language, through the word filter. A size the
+/// language doesn't come in falls back to its own list.
+pub fn word_pool(config: &GeneratorConfig) -> WordPool {
+    let list = get_word_list(&config.language, config.list_size)
+        .unwrap_or_else(|| get_language(&config.language));
+    let ranked = list.ordered_by_frequency.unwrap_or(false);
+    let (words, relaxed) = filter_words(&list.words, ranked, &config.filter);
+    WordPool { words, relaxed }
+}
 
 pub fn generate_words(config: &GeneratorConfig) -> Vec<String> {
+    generate_more_words(config, &[])
+}
+
+/// Generates the next chunk of words for a test that already shows `preceding`.
+/// Punctuation continues the running sentence instead of starting a new one.
+pub fn generate_more_words(config: &GeneratorConfig, preceding: &[String]) -> Vec<String> {
+    // Bigger lists bring only words; punctuation rules stay the language's
     let language = get_language(&config.language);
-    let word_list = &language.words;
-    let mut rng = rand::rng();
-    let mut result = Vec::with_capacity(config.count);
+    let WordPool { words, .. } = word_pool(config);
+    let mut rng = generator_rng(config.seed, preceding.len());
+
+    let profile = &language.punctuation;
+    let mut draw = |rng: &mut StdRng| {
+        let word = draw_word(&words, config.numbers, &config.targets, rng);
+        spell_noun(word, &profile.nouns)
+    };
 
-    for _ in 0..config.count {
-        // Power-law distribution: favor frequent words (beginning of list)
-        let r: f64 = rng.random();
-        let index = (r.powf(1.5) * word_list.len() as f64).floor() as usize;
-        let index = index.min(word_list.len() - 1);
-        let mut word = word_list[index].clone();
+    if !config.punctuation {
+        return (0..config.count).map(|_| draw(&mut rng)).collect();
+    }
 
-        // Numbers mode: ~8% chance to replace with a number
-        if config.numbers && rng.random::<f64>() < 0.08 {
-            let digits = rng.random_range(1..=4);
-            let max = 10_u32.pow(digits);
-            word = rng.random_range(0..max).to_string();
-            result.push(word);
-            continue;
+    let sentence = match preceding.last() {
+        Some(w) if !ends_sentence(w) => Sentence::Running(open_ending(preceding)),
+        _ => Sentence::Start,
+    };
+    punctuate(
+        config.count,
+        &mut draw,
+        profile,
+        sentence,
+        !preceding.is_empty(),
+        &mut rng,
+    )
+}
+
+fn draw_word(word_list: &[String], numbers: bool, targets: &[String], rng: &mut impl Rng) -> String {
+    // Adaptive practice: keep drawing until a word has one of the targets
+    if !targets.is_empty() && rng.random::<f64>() < TARGETED_SHARE {
+        for _ in 0..TARGETED_ATTEMPTS {
+            let word = &word_list[frequent_index(word_list.len(), rng)];
+            if targets.iter().any(|t| word.contains(t.as_str())) {
+                return word.clone();
+            }
         }
+    }
 
-        result.push(word);
+    let index = frequent_index(word_list.len(), rng);
+
+    // Numbers mode: ~8% chance to replace with a number
+    if numbers && rng.random::<f64>() < 0.08 {
+        let digits = rng.random_range(1..=4);
+        let max = 10_u32.pow(digits);
+        return rng.random_range(0..max).to_string();
     }
 
-    if config.punctuation {
-        apply_punctuation(&mut result, &mut rng);
+    word_list[index].clone()
+}
+
+/// Power-law distribution: favor frequent words (beginning of list)
+fn frequent_index(len: usize, rng: &mut impl Rng) -> usize {
+    let r: f64 = rng.random();
+    let index = (r.powf(1.5) * len as f64).floor() as usize;
+    index.min(len - 1)
+}
+
+/// Seeded generators derive each chunk's stream from the seed and the number
+/// of words already generated, so a continued word stream is reproducible too.
+fn generator_rng(seed: Option<u64>, offset: usize) -> StdRng {
+    match seed {
+        Some(seed) => {
+            let chunk = (offset as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15);
+            StdRng::seed_from_u64(seed ^ chunk)
+        }
+        None => StdRng::from_rng(&mut rand::rng()),
     }
+}
 
-    result
+enum Sentence {
+    Start,
+    /// Mid-sentence, with the mark the sentence will end on.
+    Running(char),
 }
 
-fn apply_punctuation(words: &mut [String], rng: &mut impl Rng) {
-    let mut sentence_start = true;
+/// An open quotation or parenthesis and how many more words it spans.
+struct Group {
+    close: String,
+    remaining: usize,
+}
+
+/// Builds `count` punctuated tokens. Spaced marks (French) become tokens of
+/// their own while there is room, so the chunk never exceeds `count`.
+fn punctuate(
+    count: usize,
+    draw: &mut impl FnMut(&mut StdRng) -> String,
+    profile: &PunctuationProfile,
+    mut sentence: Sentence,
+    continued: bool,
+    rng: &mut StdRng,
+) -> Vec<String> {
+    let mut out: Vec<String> = Vec::with_capacity(count);
+    let mut group: Option<Group> = None;
 
-    for i in 0..words.len() {
-        // Capitalize first word of sentence
-        if sentence_start && !words[i].is_empty() {
-            let mut chars = words[i].chars();
-            if let Some(first) = chars.next() {
-                words[i] = first.to_uppercase().to_string() + chars.as_str();
+    while out.len() < count {
+        let mut word = draw(rng);
+        let mut before: Vec<String> = Vec::new();
+        let mut after: Vec<String> = Vec::new();
+        let mut prefix = String::new();
+
+        if profile.hyphenation && rng.random::<f64>() < 0.03 {
+            // A noun compounds with another noun, drawn from the noun list
+            let other = match profile.nouns.len() {
+                0 => draw(rng),
+                n => profile.nouns[rng.random_range(0..n)].clone(),
+            };
+            if can_hyphenate(&word, &other, &profile.nouns) {
+                word = format!("{word}-{other}");
             }
-            sentence_start = false;
         }
 
-        // End of sentence: period ~12% chance
-        if i > 0 && rng.random::<f64>() < 0.12 {
-            words[i].push('.');
-            sentence_start = true;
-            continue;
+        let ending = match sentence {
+            Sentence::Start => {
+                let ending = match rng.random::<f64>() {
+                    r if r < 0.7 => '.',
+                    r if r < 0.85 => '?',
+                    _ => '!',
+                };
+                if profile.inverted_marks && ending != '.' {
+                    prefix.push(if ending == '?' { '¿' } else { '¡' });
+                }
+                word = capitalize(&word);
+                ending
+            }
+            Sentence::Running(ending) => {
+                let forms: Vec<&String> = profile
+                    .contractions
+                    .iter()
+                    .filter(|(base, form)| *base == word && is_contraction_of(form, base))
+                    .map(|(_, form)| form)
+                    .collect();
+                if !forms.is_empty() && rng.random::<f64>() < 0.15 {
+                    word = forms[rng.random_range(0..forms.len())].clone();
+                }
+                ending
+            }
+        };
+
+        // Quotes and parentheses need a couple of words left to close in
+        if group.is_none() && count - out.len() >= 3 {
+            let r = rng.random::<f64>();
+            let opened = if r < 0.03 {
+                Some((profile.quotes.clone(), rng.random_range(1..=4), true))
+            } else if r < 0.06 {
+                Some((("(".into(), ")".into()), rng.random_range(1..=3), false))
+            } else {
+                None
+            };
+            if let Some(((open, close), remaining, quote)) = opened {
+                if quote && profile.spaced_marks && out.len() + 2 <= count {
+                    before.push(std::mem::take(&mut prefix) + &open);
+                } else {
+                    prefix.push_str(&open);
+                }
+                group = Some(Group { close, remaining });
+            }
         }
+        word = prefix + &word;
 
-        // Comma: ~6% chance mid-sentence
-        if !sentence_start && rng.random::<f64>() < 0.06 {
-            words[i].push(',');
+        let used = out.len() + before.len() + 1;
+        let mark = |word: &mut String, after: &mut Vec<String>, m: &str, spaced: bool| {
+            if spaced && used + after.len() < count {
+                after.push(m.to_string());
+            } else {
+                word.push_str(m);
+            }
+        };
+
+        let ends = (!out.is_empty() || continued) && rng.random::<f64>() < 0.12;
+        let closes = match group.as_mut() {
+            Some(g) => {
+                g.remaining -= 1;
+                g.remaining == 0 || ends || used >= count
+            }
+            None => false,
+        };
+        if closes {
+            let g = group.take().unwrap();
+            let spaced = profile.spaced_marks && g.close == profile.quotes.1;
+            mark(&mut word, &mut after, &g.close, spaced);
+        }
+
+        if ends {
+            let spaced = profile.spaced_marks && ending != '.';
+            mark(&mut word, &mut after, &ending.to_string(), spaced);
+            sentence = Sentence::Start;
+        } else {
+            sentence = Sentence::Running(ending);
+            let r = rng.random::<f64>();
+            if r < 0.06 {
+                mark(&mut word, &mut after, ",", false);
+            } else if r < 0.075 {
+                mark(&mut word, &mut after, ":", profile.spaced_marks);
+            } else if r < 0.09 {
+                mark(&mut word, &mut after, ";", profile.spaced_marks);
+            }
+        }
+
+        out.extend(before);
+        out.push(word);
+        out.extend(after);
+    }
+
+    out
+}
+
+/// Whether a token ends its sentence, looking past closing quotes and
+/// parentheses. A lone French `?` counts too.
+fn ends_sentence(token: &str) -> bool {
+    token
+        .trim_end_matches(|c: char| !c.is_alphanumeric() && !matches!(c, '.' | '?' | '!'))
+        .ends_with(['.', '?', '!'])
+}
+
+/// The mark a running sentence must end on: a question or exclamation if it
+/// opened with `¿` or `¡`.
+fn open_ending(preceding: &[String]) -> char {
+    for token in preceding.iter().rev() {
+        if ends_sentence(token) {
+            break;
         }
+        if token.contains('¿') {
+            return '?';
+        }
+        if token.contains('¡') {
+            return '!';
+        }
+    }
+    '.'
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().to_string() + chars.as_str(),
+        None => String::new(),
+    }
+}
+
+/// Spells a drawn word the way the language's noun list does, so nouns are
+/// capitalized even when the word list has them in lowercase.
+fn spell_noun(word: String, nouns: &[String]) -> String {
+    let lower = word.to_lowercase();
+    match nouns.iter().find(|noun| noun.to_lowercase() == lower) {
+        Some(noun) => noun.clone(),
+        None => word,
+    }
+}
+
+/// Compounds join plain words; with a noun list only two nouns join.
+fn can_hyphenate(first: &str, second: &str, nouns: &[String]) -> bool {
+    let alphabetic = |w: &str| !w.is_empty() && w.chars().all(char::is_alphabetic);
+    let capitalized = |w: &str| w.chars().next().is_some_and(char::is_uppercase);
+    let noun = |w: &str| nouns.iter().any(|n| n == w);
+    alphabetic(first)
+        && alphabetic(second)
+        && if nouns.is_empty() {
+            !capitalized(second)
+        } else {
+            noun(first) && noun(second)
+        }
+}
+
+/// Whether `form` contracts `word`: it starts with the word itself, as in
+/// `don't`, or with the word elided before an apostrophe, as in `c'est`.
+pub fn is_contraction_of(form: &str, word: &str) -> bool {
+    let Some((head, tail)) = form.split_once('\'') else {
+        return false;
+    };
+    if word.is_empty() || tail.is_empty() {
+        return false;
     }
+    let mut stem = word.chars();
+    stem.next_back();
+    form.starts_with(word) || head == stem.as_str()
 }
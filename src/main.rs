@@ -7,42 +7,116 @@ mod types;
 mod ui;
 
 use app::App;
-use crossterm::event::{self, Event};
+use crossterm::event::{
+    self, DisableBracketedPaste, EnableBracketedPaste, Event, KeyboardEnhancementFlags,
+    PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+};
 use crossterm::terminal::{self, EnterAlternateScreen, LeaveAlternateScreen};
 use crossterm::{cursor, execute};
 use ratatui::prelude::CrosstermBackend;
 use ratatui::Terminal;
-use std::io;
+use std::io::{self, IsTerminal, Read};
 use std::time::Duration;
 
 fn main() -> io::Result<()> {
+    // Custom text has to be read before the terminal goes raw
+    let (custom_text, repo_dir) = read_args()?;
+
     // Setup terminal
     terminal::enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, cursor::Hide)?;
+    execute!(stdout, EnterAlternateScreen, EnableBracketedPaste, cursor::Hide)?;
+    // Most terminals only tell Shift+Enter from Enter (zen's finish key) with
+    // the kitty keyboard protocol. Disambiguation reports modified keys such
+    // as Shift+Enter and leaves every other key as it was.
+    let enhanced = terminal::supports_keyboard_enhancement().unwrap_or(false);
+    if enhanced {
+        execute!(
+            stdout,
+            PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES)
+        )?;
+    }
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
     // Ensure cleanup on panic
     let default_hook = std::panic::take_hook();
     std::panic::set_hook(Box::new(move |info| {
+        if enhanced {
+            let _ = execute!(io::stdout(), PopKeyboardEnhancementFlags);
+        }
         let _ = terminal::disable_raw_mode();
-        let _ = execute!(io::stdout(), LeaveAlternateScreen, cursor::Show);
+        let _ = execute!(
+            io::stdout(),
+            LeaveAlternateScreen,
+            DisableBracketedPaste,
+            cursor::Show
+        );
         default_hook(info);
     }));
 
     // Run app
-    let result = run_app(&mut terminal);
+    let result = run_app(&mut terminal, custom_text, repo_dir);
 
     // Restore terminal
+    if enhanced {
+        execute!(terminal.backend_mut(), PopKeyboardEnhancementFlags)?;
+    }
     terminal::disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen, cursor::Show)?;
+    execute!(
+        terminal.backend_mut(),
+        LeaveAlternateScreen,
+        DisableBracketedPaste,
+        cursor::Show
+    )?;
 
     result
 }
 
-fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> io::Result<()> {
-    let mut app = App::new();
+/// What the command line asks to practice on: custom text from a file, from
+/// stdin with `-` or piped in, or a directory's source code. The directory is
+/// made absolute so the saved setting still points at it from elsewhere.
+fn read_args() -> io::Result<(Option<String>, Option<String>)> {
+    let invalid = |message: String| io::Error::new(io::ErrorKind::InvalidInput, message);
+    let mut text = String::new();
+    match std::env::args().nth(1).as_deref() {
+        None if io::stdin().is_terminal() => return Ok((None, None)),
+        None => {
+            // Nothing piped in (e.g. `< /dev/null`) is no custom text
+            io::stdin().read_to_string(&mut text)?;
+            if text.split_whitespace().next().is_none() {
+                return Ok((None, None));
+            }
+        }
+        Some("-") => {
+            io::stdin().read_to_string(&mut text)?;
+        }
+        Some(flag) if flag.starts_with('-') => {
+            return Err(invalid(format!(
+                "unknown option {flag} (usage: buddytype [FILE | DIRECTORY | -])"
+            )));
+        }
+        Some(path) if std::path::Path::new(path).is_dir() => {
+            let dir = std::fs::canonicalize(path)?;
+            return Ok((None, Some(dir.display().to_string())));
+        }
+        Some(path) => {
+            text = std::fs::read_to_string(path)
+                .map_err(|e| io::Error::new(e.kind(), format!("{path}: {e}")))?;
+        }
+    }
+    if text.split_whitespace().next().is_none() {
+        return Err(invalid("custom text is empty".into()));
+    }
+    Ok((Some(text), None))
+}
+
+fn run_app(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    custom_text: Option<String>,
+    repo_dir: Option<String>,
+) -> io::Result<()> {
+    let mut app = App::new(custom_text, repo_dir);
 
     // Get initial terminal size
     let (w, h) = terminal::size()?;
@@ -60,6 +134,7 @@ fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>) -> io::Result<
                     }
                     app.handle_key(key);
                 }
+                Event::Paste(text) => app.handle_paste(&text),
                 Event::Resize(w, h) => app.handle_resize(w, h),
                 _ => {}
             }
@@ -1,29 +1,125 @@
 use crate::app::App;
-use crate::data::languages::get_available_languages;
-use crate::data::themes::{get_theme_names, parse_hex};
-use crate::types::{TestConfig, TestMode, ThemeColors};
+use crate::data::languages::{
+    available_list_sizes, get_available_languages, get_language, get_word_list,
+};
+use crate::data::quotes::has_quotes;
+use crate::data::snippets::snippet_language;
+use crate::data::themes::{get_theme, get_theme_names, parse_hex, save_user_theme};
+use crate::types::{
+    CodeSource, Confidence, ExcerptKind, Funbox, KeySet, KeyboardLayout, QuoteLength, StopOnError,
+    TestConfig, TestMode, ThemeColors, WordListSize, ZenEndKey,
+};
 use crate::ui::header::{Header, HEADER_HEIGHT};
 use crate::ui::picker::{Picker, PickerResult, PickerState};
+use crate::ui::text_editor::{TextEditor, TextEditorResult, TextEditorState};
+use crate::ui::theme_editor::{ThemeEditor, ThemeEditorResult, ThemeEditorState};
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::layout::{Constraint, Layout, Rect};
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::Frame;
 
+const MODE_OPTIONS: &[TestMode] = &[
+    TestMode::Time,
+    TestMode::Words,
+    TestMode::Quote,
+    TestMode::Custom,
+    TestMode::Zen,
+    TestMode::Code,
+];
 const TIME_OPTIONS: &[u32] = &[15, 30, 60, 120];
 const WORD_OPTIONS: &[u32] = &[10, 25, 50, 100];
+const QUOTE_LENGTH_OPTIONS: &[QuoteLength] = &[
+    QuoteLength::Short,
+    QuoteLength::Medium,
+    QuoteLength::Long,
+    QuoteLength::Thicc,
+];
+const CUSTOM_REPEAT_OPTIONS: &[u32] = &[1, 2, 3, 5, 10];
+const CODE_SOURCE_OPTIONS: &[CodeSource] = &[CodeSource::Snippets, CodeSource::Repo];
+const EXCERPT_KIND_OPTIONS: &[ExcerptKind] = &[ExcerptKind::Functions, ExcerptKind::Lines];
+const ZEN_END_KEY_OPTIONS: &[ZenEndKey] =
+    &[ZenEndKey::Enter, ZenEndKey::ShiftEnter, ZenEndKey::CtrlD];
+const FUNBOX_OPTIONS: &[Option<Funbox>] = &[
+    None,
+    Some(Funbox::Mirror),
+    Some(Funbox::RandomCase),
+    Some(Funbox::Capitals),
+    Some(Funbox::Gibberish),
+    Some(Funbox::Numbers),
+    Some(Funbox::Nospace),
+    Some(Funbox::LayoutMirror),
+];
+/// Frequency rank windows, as list positions from..to.
+const RANK_OPTIONS: &[Option<(usize, usize)>] = &[
+    None,
+    Some((0, 200)),
+    Some((200, 1000)),
+    Some((1000, 5000)),
+    Some((5000, 10000)),
+];
+/// Word length bounds, as (min, max) characters.
+const WORD_LENGTH_OPTIONS: &[(Option<usize>, Option<usize>)] = &[
+    (None, None),
+    (None, Some(4)),
+    (Some(3), Some(6)),
+    (Some(5), Some(8)),
+    (Some(7), None),
+];
+const KEY_SET_OPTIONS: &[Option<KeySet>] = &[
+    None,
+    Some(KeySet::HomeRow),
+    Some(KeySet::TopRow),
+    Some(KeySet::BottomRow),
+    Some(KeySet::LeftHand),
+    Some(KeySet::RightHand),
+];
+const LAYOUT_OPTIONS: &[KeyboardLayout] =
+    &[KeyboardLayout::Qwerty, KeyboardLayout::Dvorak, KeyboardLayout::Colemak];
+const CONFIDENCE_OPTIONS: &[Confidence] = &[Confidence::Off, Confidence::On, Confidence::Max];
+const STOP_ON_ERROR_OPTIONS: &[StopOnError] =
+    &[StopOnError::Off, StopOnError::Letter, StopOnError::Word];
+const MIN_WPM_OPTIONS: &[Option<u32>] =
+    &[None, Some(20), Some(40), Some(60), Some(80), Some(100), Some(120)];
+const MIN_ACCURACY_OPTIONS: &[Option<u32>] =
+    &[None, Some(80), Some(90), Some(95), Some(98), Some(100)];
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MenuField {
     Mode,
     Time,
     Words,
+    QuoteLength,
+    CustomText,
+    CustomShuffle,
+    CustomRepeat,
+    ZenEndKey,
+    AutoIndent,
+    CodeSource,
+    ExcerptKind,
+    StripComments,
     Language,
     Theme,
     OneLine,
     Punctuation,
     Numbers,
-    Backspace,
+    Adaptive,
+    WordList,
+    Ranks,
+    WordLength,
+    RequiredChars,
+    AllowedKeys,
+    KeyboardLayout,
+    LazyMode,
+    BlindMode,
+    Funbox,
+    Confidence,
+    Freedom,
+    StopOnError,
+    MinWpm,
+    MinAccuracy,
+    SuddenDeath,
+    Seed,
 }
 
 pub struct MenuState {
@@ -35,6 +131,9 @@ pub struct MenuState {
 pub enum PickerKind {
     Language(PickerState),
     Theme(PickerState),
+    ThemeEditor(Box<ThemeEditorState>),
+    CustomText(TextEditorState),
+    RequiredChars(TextEditorState),
 }
 
 pub enum MenuAction {
@@ -63,14 +162,60 @@ fn get_fields(config: &TestConfig) -> Vec<MenuField> {
     match config.mode {
         TestMode::Time => fields.push(MenuField::Time),
         TestMode::Words => fields.push(MenuField::Words),
+        TestMode::Quote => fields.push(MenuField::QuoteLength),
+        TestMode::Custom => fields.extend([
+            MenuField::CustomText,
+            MenuField::CustomShuffle,
+            MenuField::CustomRepeat,
+        ]),
+        TestMode::Zen => fields.push(MenuField::ZenEndKey),
+        TestMode::Code => {
+            fields.push(MenuField::AutoIndent);
+            // A repository is chosen on the command line; then it can be toggled
+            if config.repo_dir.is_some() {
+                fields.push(MenuField::CodeSource);
+                if config.code_source == CodeSource::Repo {
+                    fields.extend([MenuField::ExcerptKind, MenuField::StripComments]);
+                }
+            }
+        }
+    }
+    fields.extend([MenuField::Language, MenuField::Theme, MenuField::OneLine]);
+    // Quotes, custom text and code come with their own punctuation; zen has no text
+    if !matches!(
+        config.mode,
+        TestMode::Quote | TestMode::Custom | TestMode::Zen | TestMode::Code
+    ) {
+        fields.extend([
+            MenuField::Punctuation,
+            MenuField::Numbers,
+            MenuField::Adaptive,
+            MenuField::WordList,
+            MenuField::Ranks,
+            MenuField::WordLength,
+            MenuField::RequiredChars,
+            MenuField::AllowedKeys,
+        ]);
+        if config.word_filter.allowed.is_some() {
+            fields.push(MenuField::KeyboardLayout);
+        }
+    }
+    fields.extend([MenuField::LazyMode, MenuField::BlindMode]);
+    // Zen has no text for a funbox to transform, and code keeps its layout
+    if !matches!(config.mode, TestMode::Zen | TestMode::Code) {
+        fields.push(MenuField::Funbox);
+    }
+    fields.push(MenuField::Confidence);
+    // Freedom only matters while backspacing into previous words is allowed
+    if config.confidence == Confidence::Off {
+        fields.push(MenuField::Freedom);
     }
     fields.extend([
-        MenuField::Language,
-        MenuField::Theme,
-        MenuField::OneLine,
-        MenuField::Punctuation,
-        MenuField::Numbers,
-        MenuField::Backspace,
+        MenuField::StopOnError,
+        MenuField::MinWpm,
+        MenuField::MinAccuracy,
+        MenuField::SuddenDeath,
+        MenuField::Seed,
     ]);
     fields
 }
@@ -119,10 +264,71 @@ pub fn handle_key(state: &mut MenuState, key: KeyEvent, config: &TestConfig) ->
                         new_config.theme = val;
                         return MenuAction::UpdateConfig(new_config);
                     }
+                    PickerResult::Edit(val) => {
+                        let editor = ThemeEditorState::new(&get_theme(&val));
+                        state.picker = Some(PickerKind::ThemeEditor(Box::new(editor)));
+                    }
                     _ => {}
                 }
                 return MenuAction::None;
             }
+            PickerKind::ThemeEditor(editor) => {
+                match editor.handle_key(key) {
+                    ThemeEditorResult::Save => match save_user_theme(&editor.name, &editor.theme) {
+                        Ok(()) => {
+                            let mut new_config = config.clone();
+                            new_config.theme = editor.name.clone();
+                            state.picker = None;
+                            state.theme_preview_base = None;
+                            return MenuAction::UpdateConfig(new_config);
+                        }
+                        Err(e) => editor.error = Some(e),
+                    },
+                    // Back to the picker, still on the theme that was edited
+                    ThemeEditorResult::Cancelled => {
+                        let base = state.theme_preview_base.clone().unwrap_or_default();
+                        let mut picker = PickerState::new(get_theme_names(), base);
+                        picker.cursor = picker
+                            .items
+                            .iter()
+                            .position(|i| *i == config.theme)
+                            .unwrap_or(picker.cursor);
+                        state.picker = Some(PickerKind::Theme(picker));
+                    }
+                    ThemeEditorResult::Active => {}
+                }
+                return MenuAction::None;
+            }
+            PickerKind::CustomText(editor) => {
+                match editor.handle_key(key) {
+                    TextEditorResult::Saved(text) => {
+                        let mut new_config = config.clone();
+                        new_config.custom_text = text;
+                        state.picker = None;
+                        return MenuAction::UpdateConfig(new_config);
+                    }
+                    TextEditorResult::Cancelled => {
+                        state.picker = None;
+                    }
+                    TextEditorResult::Active => {}
+                }
+                return MenuAction::None;
+            }
+            PickerKind::RequiredChars(editor) => {
+                match editor.handle_key(key) {
+                    TextEditorResult::Saved(text) => {
+                        let mut new_config = config.clone();
+                        new_config.word_filter.required = text.split_whitespace().collect();
+                        state.picker = None;
+                        return MenuAction::UpdateConfig(new_config);
+                    }
+                    TextEditorResult::Cancelled => {
+                        state.picker = None;
+                    }
+                    TextEditorResult::Active => {}
+                }
+                return MenuAction::None;
+            }
         }
     }
 
@@ -132,6 +338,31 @@ pub fn handle_key(state: &mut MenuState, key: KeyEvent, config: &TestConfig) ->
         .position(|f| *f == state.selected_field)
         .unwrap_or(0);
 
+    // Seed: type digits to pin one, backspace them away to go back to random
+    if state.selected_field == MenuField::Seed {
+        let mut new_config = config.clone();
+        match key.code {
+            KeyCode::Char(c) if c.is_ascii_digit() => {
+                let digit = c.to_digit(10).unwrap_or(0) as u64;
+                let seed = config.seed.unwrap_or(0);
+                new_config.seed = seed.checked_mul(10).and_then(|s| s.checked_add(digit));
+                if new_config.seed.is_none() {
+                    return MenuAction::None;
+                }
+                return MenuAction::UpdateConfig(new_config);
+            }
+            KeyCode::Backspace => {
+                new_config.seed = config.seed.map(|s| s / 10).filter(|&s| s > 0);
+                return MenuAction::UpdateConfig(new_config);
+            }
+            KeyCode::Left | KeyCode::Right | KeyCode::Char(' ') => {
+                new_config.seed = None;
+                return MenuAction::UpdateConfig(new_config);
+            }
+            _ => {}
+        }
+    }
+
     match key.code {
         KeyCode::Char('s') if !key.modifiers.contains(KeyModifiers::CONTROL) => {
             return MenuAction::Scores;
@@ -148,14 +379,24 @@ pub fn handle_key(state: &mut MenuState, key: KeyEvent, config: &TestConfig) ->
             }
             if state.selected_field == MenuField::Theme {
                 state.theme_preview_base = Some(config.theme.clone());
-                let items: Vec<String> =
-                    get_theme_names().into_iter().map(String::from).collect();
                 state.picker = Some(PickerKind::Theme(PickerState::new(
-                    items,
+                    get_theme_names(),
                     config.theme.clone(),
                 )));
                 return MenuAction::None;
             }
+            // Nothing to type yet: ask for the text instead of starting
+            let needs_text = config.mode == TestMode::Custom
+                && config.custom_text.split_whitespace().next().is_none();
+            if state.selected_field == MenuField::CustomText || needs_text {
+                state.selected_field = MenuField::CustomText;
+                open_text_editor(state, config);
+                return MenuAction::None;
+            }
+            if state.selected_field == MenuField::RequiredChars {
+                open_required_chars_editor(state, config);
+                return MenuAction::None;
+            }
             return MenuAction::Start(config.clone());
         }
         KeyCode::Up | KeyCode::Char('k') => {
@@ -178,51 +419,99 @@ pub fn handle_key(state: &mut MenuState, key: KeyEvent, config: &TestConfig) ->
             }
             if state.selected_field == MenuField::Theme {
                 state.theme_preview_base = Some(config.theme.clone());
-                let items: Vec<String> =
-                    get_theme_names().into_iter().map(String::from).collect();
                 state.picker = Some(PickerKind::Theme(PickerState::new(
-                    items,
+                    get_theme_names(),
                     config.theme.clone(),
                 )));
                 return MenuAction::None;
             }
+            if state.selected_field == MenuField::CustomText {
+                open_text_editor(state, config);
+                return MenuAction::None;
+            }
+            if state.selected_field == MenuField::RequiredChars {
+                open_required_chars_editor(state, config);
+                return MenuAction::None;
+            }
             let forward = key.code == KeyCode::Right;
             let mut new_config = config.clone();
             match state.selected_field {
                 MenuField::Mode => {
-                    new_config.mode = match config.mode {
-                        TestMode::Time => TestMode::Words,
-                        TestMode::Words => TestMode::Time,
-                    };
+                    new_config.mode = cycle(MODE_OPTIONS, config.mode, forward);
                 }
                 MenuField::Time => {
-                    let idx = TIME_OPTIONS
-                        .iter()
-                        .position(|&t| t == config.time_limit)
-                        .unwrap_or(0);
-                    let next = if forward {
-                        (idx + 1) % TIME_OPTIONS.len()
-                    } else {
-                        (idx + TIME_OPTIONS.len() - 1) % TIME_OPTIONS.len()
-                    };
-                    new_config.time_limit = TIME_OPTIONS[next];
+                    new_config.time_limit = cycle(TIME_OPTIONS, config.time_limit, forward);
                 }
                 MenuField::Words => {
-                    let idx = WORD_OPTIONS
-                        .iter()
-                        .position(|&w| w == config.word_count)
-                        .unwrap_or(0);
-                    let next = if forward {
-                        (idx + 1) % WORD_OPTIONS.len()
-                    } else {
-                        (idx + WORD_OPTIONS.len() - 1) % WORD_OPTIONS.len()
-                    };
-                    new_config.word_count = WORD_OPTIONS[next];
+                    new_config.word_count = cycle(WORD_OPTIONS, config.word_count, forward);
+                }
+                MenuField::QuoteLength => {
+                    new_config.quote_length =
+                        cycle(QUOTE_LENGTH_OPTIONS, config.quote_length, forward);
                 }
+                MenuField::CustomShuffle => new_config.custom_shuffle = !config.custom_shuffle,
+                MenuField::CustomRepeat => {
+                    new_config.custom_repeat =
+                        cycle(CUSTOM_REPEAT_OPTIONS, config.custom_repeat, forward);
+                }
+                MenuField::ZenEndKey => {
+                    new_config.zen_end_key = cycle(ZEN_END_KEY_OPTIONS, config.zen_end_key, forward);
+                }
+                MenuField::AutoIndent => new_config.auto_indent = !config.auto_indent,
+                MenuField::CodeSource => {
+                    new_config.code_source = cycle(CODE_SOURCE_OPTIONS, config.code_source, forward);
+                }
+                MenuField::ExcerptKind => {
+                    new_config.excerpt_kind =
+                        cycle(EXCERPT_KIND_OPTIONS, config.excerpt_kind, forward);
+                }
+                MenuField::StripComments => new_config.strip_comments = !config.strip_comments,
                 MenuField::OneLine => new_config.one_line = !config.one_line,
                 MenuField::Punctuation => new_config.punctuation = !config.punctuation,
                 MenuField::Numbers => new_config.numbers = !config.numbers,
-                MenuField::Backspace => new_config.backspace = !config.backspace,
+                MenuField::Adaptive => new_config.adaptive = !config.adaptive,
+                MenuField::WordList => {
+                    let sizes = available_list_sizes(&config.language);
+                    new_config.word_list = cycle(&sizes, config.word_list, forward);
+                }
+                MenuField::Ranks => {
+                    new_config.word_filter.ranks =
+                        cycle(RANK_OPTIONS, config.word_filter.ranks, forward);
+                }
+                MenuField::WordLength => {
+                    let filter = &mut new_config.word_filter;
+                    let current = (filter.min_length, filter.max_length);
+                    (filter.min_length, filter.max_length) =
+                        cycle(WORD_LENGTH_OPTIONS, current, forward);
+                }
+                MenuField::AllowedKeys => {
+                    new_config.word_filter.allowed =
+                        cycle(KEY_SET_OPTIONS, config.word_filter.allowed, forward);
+                }
+                MenuField::KeyboardLayout => {
+                    new_config.word_filter.layout =
+                        cycle(LAYOUT_OPTIONS, config.word_filter.layout, forward);
+                }
+                MenuField::Confidence => {
+                    new_config.confidence = cycle(CONFIDENCE_OPTIONS, config.confidence, forward);
+                }
+                MenuField::Freedom => new_config.freedom = !config.freedom,
+                MenuField::LazyMode => new_config.lazy_mode = !config.lazy_mode,
+                MenuField::BlindMode => new_config.blind_mode = !config.blind_mode,
+                MenuField::Funbox => {
+                    new_config.funbox = cycle(FUNBOX_OPTIONS, config.funbox, forward);
+                }
+                MenuField::MinWpm => {
+                    new_config.min_wpm = cycle(MIN_WPM_OPTIONS, config.min_wpm, forward);
+                }
+                MenuField::MinAccuracy => {
+                    new_config.min_accuracy =
+                        cycle(MIN_ACCURACY_OPTIONS, config.min_accuracy, forward);
+                }
+                MenuField::SuddenDeath => new_config.sudden_death = !config.sudden_death,
+                MenuField::StopOnError => {
+                    new_config.stop_on_error = cycle(STOP_ON_ERROR_OPTIONS, config.stop_on_error, forward);
+                }
                 _ => {}
             }
             return MenuAction::UpdateConfig(new_config);
@@ -254,10 +543,52 @@ pub fn render(frame: &mut Frame, area: Rect, app: &App, theme: &ThemeColors) {
         let picker_area = centered_rect(40, 20, chunks[1]);
         match picker_kind {
             PickerKind::Language(ps) => {
-                frame.render_widget(Picker { state: ps, theme }, picker_area);
+                frame.render_widget(
+                    Picker {
+                        state: ps,
+                        theme,
+                        editable: false,
+                    },
+                    picker_area,
+                );
             }
             PickerKind::Theme(ps) => {
-                frame.render_widget(Picker { state: ps, theme }, picker_area);
+                frame.render_widget(
+                    Picker {
+                        state: ps,
+                        theme,
+                        editable: true,
+                    },
+                    picker_area,
+                );
+            }
+            PickerKind::ThemeEditor(editor) => {
+                let editor_area = centered_rect(80, 20, chunks[1]);
+                frame.render_widget(ThemeEditor { state: editor, theme }, editor_area);
+            }
+            PickerKind::CustomText(editor) => {
+                let editor_area = centered_rect(70, 16, chunks[1]);
+                frame.render_widget(
+                    TextEditor {
+                        state: editor,
+                        theme,
+                        title: "custom text",
+                        word_count: true,
+                    },
+                    editor_area,
+                );
+            }
+            PickerKind::RequiredChars(editor) => {
+                let editor_area = centered_rect(50, 5, chunks[1]);
+                frame.render_widget(
+                    TextEditor {
+                        state: editor,
+                        theme,
+                        title: "every word contains one of",
+                        word_count: false,
+                    },
+                    editor_area,
+                );
             }
         }
         return;
@@ -267,22 +598,103 @@ pub fn render(frame: &mut Frame, area: Rect, app: &App, theme: &ThemeColors) {
     let fields = get_fields(&app.config);
     let menu_area = centered_rect(50, fields.len() as u16, chunks[1]);
 
-    for (i, field) in fields.iter().enumerate() {
-        if i as u16 >= menu_area.height {
+    // Scroll so the selected field stays visible on short terminals
+    let visible = menu_area.height as usize;
+    let selected_idx = fields
+        .iter()
+        .position(|f| *f == app.menu_state.selected_field)
+        .unwrap_or(0);
+    let scroll = (selected_idx + 1).saturating_sub(visible);
+
+    for (i, field) in fields.iter().enumerate().skip(scroll) {
+        let row = i - scroll;
+        if row as u16 >= menu_area.height {
             break;
         }
         let is_selected = *field == app.menu_state.selected_field;
 
         let (label, value) = match field {
-            MenuField::Mode => ("mode", format!("{}", match app.config.mode {
+            MenuField::Mode => ("mode", match app.config.mode {
                 TestMode::Time => "time",
                 TestMode::Words => "words",
-            })),
+                TestMode::Quote => "quote",
+                TestMode::Custom => "custom",
+                TestMode::Zen => "zen",
+                TestMode::Code => "code",
+            }.to_string()),
             MenuField::Time => ("time", format!("{}s", app.config.time_limit)),
             MenuField::Words => ("words", format!("{}", app.config.word_count)),
+            MenuField::QuoteLength => (
+                "quote length",
+                {
+                    let length = match app.config.quote_length {
+                        QuoteLength::Short => "short",
+                        QuoteLength::Medium => "medium",
+                        QuoteLength::Long => "long",
+                        QuoteLength::Thicc => "thicc",
+                    };
+                    if has_quotes(&app.config.language) {
+                        length.to_string()
+                    } else {
+                        format!("{length} (english quotes)")
+                    }
+                },
+            ),
+            MenuField::CustomText => ("text", custom_text_summary(&app.config.custom_text)),
+            MenuField::CustomShuffle => (
+                "shuffle",
+                if app.config.custom_shuffle { "on" } else { "off" }.into(),
+            ),
+            MenuField::CustomRepeat => ("repeat", format!("×{}", app.config.custom_repeat)),
+            MenuField::ZenEndKey => (
+                "finish with",
+                match app.config.zen_end_key {
+                    ZenEndKey::Enter => "enter",
+                    ZenEndKey::ShiftEnter => "shift+enter",
+                    ZenEndKey::CtrlD => "ctrl+d",
+                }
+                .into(),
+            ),
+            MenuField::AutoIndent => (
+                "indentation",
+                if app.config.auto_indent { "skipped" } else { "typed" }.into(),
+            ),
+            MenuField::CodeSource => (
+                "source",
+                match (app.config.code_source, &app.config.repo_dir) {
+                    (CodeSource::Repo, Some(dir)) => format!("repo {}", repo_name(dir)),
+                    _ => "snippets".into(),
+                },
+            ),
+            MenuField::ExcerptKind => (
+                "excerpt",
+                match app.config.excerpt_kind {
+                    ExcerptKind::Functions => "functions",
+                    ExcerptKind::Lines => "lines",
+                }
+                .into(),
+            ),
+            MenuField::StripComments => (
+                "comments",
+                if app.config.strip_comments { "stripped" } else { "kept" }.into(),
+            ),
             MenuField::Language => (
                 "language",
-                format!("{}  ▸", app.config.language.replace('_', " ")),
+                {
+                    let language = app.config.language.replace('_', " ");
+                    let snippets = snippet_language(&app.config.language);
+                    let repo = app.config.code_source == CodeSource::Repo
+                        && app.config.repo_dir.is_some();
+                    if app.config.mode != TestMode::Code {
+                        format!("{language}  ▸")
+                    } else if repo {
+                        format!("{language}  ▸ (from file extensions)")
+                    } else if snippets != app.config.language {
+                        format!("{language}  ▸ ({} snippets)", snippets.replace('_', " "))
+                    } else {
+                        format!("{language}  ▸")
+                    }
+                },
             ),
             MenuField::Theme => (
                 "theme",
@@ -300,9 +712,125 @@ pub fn render(frame: &mut Frame, area: Rect, app: &App, theme: &ThemeColors) {
                 "numbers",
                 if app.config.numbers { "on" } else { "off" }.into(),
             ),
-            MenuField::Backspace => (
-                "backspace",
-                if app.config.backspace { "on" } else { "off" }.into(),
+            MenuField::Adaptive => (
+                "adaptive",
+                if app.config.adaptive { "weak keys" } else { "off" }.into(),
+            ),
+            MenuField::WordList => (
+                "word list",
+                if get_word_list(&app.config.language, app.config.word_list).is_some() {
+                    app.config.word_list.label().into()
+                } else {
+                    format!("{} (no {} list)", WordListSize::Base.label(), app.config.word_list.label())
+                },
+            ),
+            MenuField::Ranks => (
+                "frequency",
+                {
+                    let list = get_word_list(&app.config.language, app.config.word_list)
+                        .unwrap_or_else(|| get_language(&app.config.language));
+                    match app.config.word_filter.ranks {
+                        _ if !list.ordered_by_frequency.unwrap_or(false) => "n/a".into(),
+                        None => "all words".into(),
+                        Some((0, to)) => format!("top {}", rank_label(to)),
+                        Some((from, to)) => format!("{}–{}", rank_label(from), rank_label(to)),
+                    }
+                },
+            ),
+            MenuField::WordLength => (
+                "word length",
+                match (app.config.word_filter.min_length, app.config.word_filter.max_length) {
+                    (None, None) => "any".into(),
+                    (None, Some(max)) => format!("up to {max}"),
+                    (Some(min), None) => format!("{min}+"),
+                    (Some(min), Some(max)) => format!("{min}–{max}"),
+                },
+            ),
+            MenuField::RequiredChars => (
+                "must contain",
+                if app.config.word_filter.required.is_empty() {
+                    "any  ▸".into()
+                } else {
+                    format!("{}  ▸", app.config.word_filter.required)
+                },
+            ),
+            MenuField::AllowedKeys => (
+                "keys",
+                app.config.word_filter.allowed.map_or("all", KeySet::label).into(),
+            ),
+            MenuField::KeyboardLayout => (
+                "layout",
+                match app.config.word_filter.layout {
+                    KeyboardLayout::Qwerty => "qwerty",
+                    KeyboardLayout::Dvorak => "dvorak",
+                    KeyboardLayout::Colemak => "colemak",
+                }
+                .into(),
+            ),
+            MenuField::LazyMode => (
+                "lazy mode",
+                if get_language(&app.config.language).no_lazy_mode.unwrap_or(false) {
+                    "n/a".into()
+                } else if app.config.lazy_mode {
+                    "on".into()
+                } else {
+                    "off".into()
+                },
+            ),
+            MenuField::BlindMode => (
+                "blind mode",
+                if app.config.blind_mode { "on" } else { "off" }.into(),
+            ),
+            MenuField::Funbox => (
+                "funbox",
+                app.config.funbox.map_or("off", Funbox::label).into(),
+            ),
+            MenuField::Confidence => (
+                "confidence",
+                match app.config.confidence {
+                    Confidence::Off => "off",
+                    Confidence::On => "on",
+                    Confidence::Max => "max",
+                }
+                .into(),
+            ),
+            MenuField::Freedom => (
+                "freedom",
+                if app.config.freedom { "on" } else { "off" }.into(),
+            ),
+            MenuField::MinWpm => (
+                "min wpm",
+                match app.config.min_wpm {
+                    Some(wpm) => format!("{wpm}"),
+                    None => "off".into(),
+                },
+            ),
+            MenuField::MinAccuracy => (
+                "min accuracy",
+                match app.config.min_accuracy {
+                    Some(acc) => format!("{acc}%"),
+                    None => "off".into(),
+                },
+            ),
+            MenuField::SuddenDeath => (
+                "sudden death",
+                if app.config.sudden_death { "on" } else { "off" }.into(),
+            ),
+            MenuField::Seed => (
+                "seed",
+                match app.config.seed {
+                    Some(seed) => format!("{seed}"),
+                    None => "random".into(),
+                },
+            ),
+            MenuField::StopOnError => (
+                "stop on error",
+                match app.config.stop_on_error {
+                    StopOnError::Off => "off",
+                    StopOnError::Letter => "letter",
+                    StopOnError::Word => "word",
+                }
+                .into(),
             ),
         };
 
@@ -323,13 +851,17 @@ pub fn render(frame: &mut Frame, area: Rect, app: &App, theme: &ThemeColors) {
             Span::styled(value, value_style),
         ]);
 
-        let y = menu_area.y + i as u16;
+        let y = menu_area.y + row as u16;
         frame.render_widget(line, Rect::new(menu_area.x, y, menu_area.width, 1));
     }
 
     // Footer
     let footer = Line::from(Span::styled(
-        "arrows/space: change  enter: start  s: scores",
+        if app.menu_state.selected_field == MenuField::Seed {
+            "0-9: type seed  space: random  enter: start  s: scores"
+        } else {
+            "arrows/space: change  enter: start  s: scores"
+        },
         Style::default().fg(dim),
     ));
     let footer_width = footer.width() as u16;
@@ -338,6 +870,77 @@ pub fn render(frame: &mut Frame, area: Rect, app: &App, theme: &ThemeColors) {
         footer,
         Rect::new(fx, chunks[2].y, chunks[2].width, 1),
     );
+
+    if let Some(notice) = &app.notice {
+        let line = Line::from(Span::styled(
+            notice.as_str(),
+            Style::default().fg(parse_hex(&theme.incorrect)),
+        ));
+        let nw = line.width() as u16;
+        let nx = area.x + area.width.saturating_sub(nw) / 2;
+        frame.render_widget(line, Rect::new(nx, chunks[2].y + 1, chunks[2].width, 1));
+    }
+}
+
+/// Paste arrives as one event when bracketed paste is supported; only the
+/// custom text editor takes it.
+pub fn handle_paste(state: &mut MenuState, text: &str) {
+    if let Some(PickerKind::CustomText(editor)) = &mut state.picker {
+        editor.paste(text);
+    }
+}
+
+fn open_text_editor(state: &mut MenuState, config: &TestConfig) {
+    state.picker = Some(PickerKind::CustomText(TextEditorState::new(
+        config.custom_text.clone(),
+    )));
+}
+
+fn open_required_chars_editor(state: &mut MenuState, config: &TestConfig) {
+    state.picker = Some(PickerKind::RequiredChars(TextEditorState::new(
+        config.word_filter.required.clone(),
+    )));
+}
+
+/// Rank as shown in the menu: `200`, `1k`, `10k`.
+fn rank_label(rank: usize) -> String {
+    if rank < 1000 {
+        rank.to_string()
+    } else {
+        format!("{}k", rank / 1000)
+    }
+}
+
+/// One-line preview of the custom text: its opening words and word count.
+fn custom_text_summary(text: &str) -> String {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    if words.is_empty() {
+        return "empty  ▸".into();
+    }
+    let mut preview = String::new();
+    for word in &words {
+        if preview.chars().count() + word.chars().count() > 20 {
+            preview.push('…');
+            break;
+        }
+        if !preview.is_empty() {
+            preview.push(' ');
+        }
+        preview.push_str(word);
+    }
+    format!("{preview} ({} words)  ▸", words.len())
+}
+
+/// Steps to the next (or previous) option, wrapping around. Unknown values
+/// start from the first option.
+fn cycle<T: Copy + PartialEq>(options: &[T], current: T, forward: bool) -> T {
+    let idx = options.iter().position(|&o| o == current).unwrap_or(0);
+    let next = if forward {
+        (idx + 1) % options.len()
+    } else {
+        (idx + options.len() - 1) % options.len()
+    };
+    options[next]
 }
 
 fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
@@ -350,3 +953,11 @@ fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
         height.min(area.height),
     )
 }
+
+/// Last component of a repository path, e.g. `buddytype` for `~/src/buddytype`.
+fn repo_name(dir: &str) -> &str {
+    std::path::Path::new(dir)
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or(dir)
+}
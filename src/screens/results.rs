@@ -1,12 +1,24 @@
 use crate::app::App;
 use crate::data::themes::parse_hex;
-use crate::types::ThemeColors;
+use crate::engine::adaptive::improved;
+use crate::types::{FailReason, TestMode, TestResult, ThemeColors};
 use crate::ui::results_chart::{ResultsChart, CHART_HEIGHT};
+use crate::ui::word_history::WordHistory;
 use ratatui::layout::{Constraint, Layout, Rect};
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::Frame;
 
+/// Rows of the character breakdown: counts, missed keys, and the keys
+/// adaptive practice targeted.
+fn breakdown_height(result: &TestResult) -> u16 {
+    if result.targets.is_empty() { 2 } else { 3 }
+}
+
+pub fn content_height(result: &TestResult) -> u16 {
+    2 + 3 + breakdown_height(result) + 2 + CHART_HEIGHT + 2
+}
+
 pub fn render(frame: &mut Frame, area: Rect, app: &App, theme: &ThemeColors) {
     let result = match &app.result {
         Some(r) => r,
@@ -19,11 +31,12 @@ pub fn render(frame: &mut Frame, area: Rect, app: &App, theme: &ThemeColors) {
     let correct_color = parse_hex(&theme.correct);
     let incorrect_color = parse_hex(&theme.incorrect);
     let extra_color = parse_hex(&theme.extra);
+    let zen = result.config.mode == TestMode::Zen;
 
     let chunks = Layout::vertical([
         Constraint::Length(2), // main WPM
         Constraint::Length(3), // stats grid
-        Constraint::Length(2), // char breakdown
+        Constraint::Length(breakdown_height(result)), // char breakdown
         Constraint::Length(2), // words count
         Constraint::Length(CHART_HEIGHT), // chart
         Constraint::Length(2), // footer
@@ -44,6 +57,27 @@ pub fn render(frame: &mut Frame, area: Rect, app: &App, theme: &ThemeColors) {
         let w = line.width() as u16;
         let x = chunks[0].x + chunks[0].width.saturating_sub(w) / 2;
         frame.render_widget(line, Rect::new(x, chunks[0].y, chunks[0].width, 1));
+
+        if let Some(reason) = result.failed {
+            let why = match reason {
+                FailReason::MinWpm => format!(
+                    "test failed: wpm dropped below {}",
+                    result.config.min_wpm.unwrap_or(0)
+                ),
+                FailReason::MinAccuracy => format!(
+                    "test failed: accuracy dropped below {}%",
+                    result.config.min_accuracy.unwrap_or(0)
+                ),
+                FailReason::SuddenDeath => "test failed: sudden death".to_string(),
+            };
+            let line = Line::from(Span::styled(
+                why,
+                Style::default().fg(incorrect_color).add_modifier(Modifier::BOLD),
+            ));
+            let w = line.width() as u16;
+            let x = chunks[0].x + chunks[0].width.saturating_sub(w) / 2;
+            frame.render_widget(line, Rect::new(x, chunks[0].y + 1, chunks[0].width, 1));
+        }
     }
 
     // Stats grid: raw, accuracy, consistency, time
@@ -55,7 +89,12 @@ pub fn render(frame: &mut Frame, area: Rect, app: &App, theme: &ThemeColors) {
             ),
             Span::styled("  raw    ", Style::default().fg(dim)),
             Span::styled(
-                format!("{:.1}%", result.accuracy),
+                // Zen has no target text to be accurate against
+                if zen {
+                    "n/a".to_string()
+                } else {
+                    format!("{:.1}%", result.accuracy)
+                },
                 Style::default().fg(text_color).add_modifier(Modifier::BOLD),
             ),
             Span::styled("  accuracy    ", Style::default().fg(dim)),
@@ -79,8 +118,16 @@ pub fn render(frame: &mut Frame, area: Rect, app: &App, theme: &ThemeColors) {
         );
     }
 
-    // Character breakdown
-    {
+    // Character breakdown (zen text is all "correct", so just count it)
+    if zen {
+        let line = Line::from(vec![
+            Span::styled(format!("{}", result.correct_chars), Style::default().fg(text_color)),
+            Span::styled(" characters typed", Style::default().fg(dim)),
+        ]);
+        let w = line.width() as u16;
+        let x = chunks[2].x + chunks[2].width.saturating_sub(w) / 2;
+        frame.render_widget(line, Rect::new(x, chunks[2].y, chunks[2].width, 1));
+    } else {
         let spans = vec![
             Span::styled(
                 format!("{}", result.correct_chars),
@@ -109,35 +156,166 @@ pub fn render(frame: &mut Frame, area: Rect, app: &App, theme: &ThemeColors) {
         frame.render_widget(line, Rect::new(x, chunks[2].y, chunks[2].width, 1));
     }
 
+    // Most-missed keys
+    {
+        let mut missed: Vec<(&String, usize)> = result
+            .key_stats
+            .iter()
+            .filter(|(_, k)| k.incorrect > 0)
+            .map(|(key, k)| (key, k.incorrect))
+            .collect();
+        missed.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+
+        if !missed.is_empty() {
+            let mut spans = vec![Span::styled("missed keys: ", Style::default().fg(dim))];
+            for (key, count) in missed.iter().take(5) {
+                spans.push(Span::styled(
+                    key.to_string(),
+                    Style::default().fg(incorrect_color).add_modifier(Modifier::BOLD),
+                ));
+                spans.push(Span::styled(format!(" ×{count}  "), Style::default().fg(dim)));
+            }
+            let line = Line::from(spans);
+            let w = line.width() as u16;
+            let x = chunks[2].x + chunks[2].width.saturating_sub(w) / 2;
+            frame.render_widget(line, Rect::new(x, chunks[2].y + 1, chunks[2].width, 1));
+        }
+    }
+
+    // Keys adaptive practice targeted, before → after
+    if !result.targets.is_empty() {
+        let mut spans = vec![Span::styled("targeted: ", Style::default().fg(dim))];
+        for (i, target) in result.targets.iter().enumerate() {
+            if i > 0 {
+                spans.push(Span::raw("   "));
+            }
+            let now = result
+                .key_stats
+                .get(&target.key)
+                .or_else(|| result.bigram_stats.get(&target.key));
+            spans.push(Span::styled(
+                target.key.clone(),
+                Style::default().fg(accent).add_modifier(Modifier::BOLD),
+            ));
+            let before = format!(
+                " {:.0}% {:.0}ms",
+                target.error_rate * 100.0,
+                target.avg_press_ms
+            );
+            spans.push(Span::styled(before, Style::default().fg(dim)));
+            let (after, color) = match (now, improved(target, now)) {
+                (Some(now), Some(better)) => (
+                    format!(
+                        " → {:.0}% {:.0}ms {}",
+                        now.error_rate() * 100.0,
+                        now.avg_press_ms,
+                        if better { "▲" } else { "▼" }
+                    ),
+                    if better { correct_color } else { incorrect_color },
+                ),
+                _ => (" → not typed".to_string(), dim),
+            };
+            spans.push(Span::styled(after, Style::default().fg(color)));
+        }
+        let line = Line::from(spans);
+        let w = line.width() as u16;
+        let x = chunks[2].x + chunks[2].width.saturating_sub(w) / 2;
+        frame.render_widget(line, Rect::new(x, chunks[2].y + 2, chunks[2].width, 1));
+    }
+
     // Words count
     {
-        let line = Line::from(vec![
-            Span::styled(
-                format!("{}/{}", result.correct_words, result.total_words),
-                Style::default().fg(text_color),
-            ),
-            Span::styled(" words correct", Style::default().fg(dim)),
-        ]);
+        let mut spans = if zen {
+            vec![
+                Span::styled(format!("{}", result.total_words), Style::default().fg(text_color)),
+                Span::styled(" words", Style::default().fg(dim)),
+            ]
+        } else {
+            vec![
+                Span::styled(
+                    format!("{}/{}", result.correct_words, result.total_words),
+                    Style::default().fg(text_color),
+                ),
+                Span::styled(" words correct", Style::default().fg(dim)),
+            ]
+        };
+        // Errors were hidden while typing; flag that these numbers are the reveal
+        if result.config.blind_mode {
+            spans.push(Span::styled("   blind mode", Style::default().fg(accent)));
+        }
+        if let Some(funbox) = result.config.funbox.filter(|_| !zen) {
+            spans.push(Span::styled(
+                format!("   funbox: {}", funbox.label()),
+                Style::default().fg(accent),
+            ));
+        }
+        let line = Line::from(spans);
         let w = line.width() as u16;
         let x = chunks[3].x + chunks[3].width.saturating_sub(w) / 2;
         frame.render_widget(line, Rect::new(x, chunks[3].y, chunks[3].width, 1));
+
+        let mut spans = Vec::new();
+        let source = match (&result.quote, &result.snippet, &result.excerpt) {
+            (Some(quote), _, _) => Some(quote.source.clone()),
+            (None, Some(snippet), _) => Some(snippet.source.clone()),
+            (None, None, Some(excerpt)) => Some(format!(
+                "{}:{}-{}",
+                excerpt.path, excerpt.lines.0, excerpt.lines.1
+            )),
+            (None, None, None) => None,
+        };
+        if let Some(source) = source {
+            spans.push(Span::styled(
+                format!("— {source}   "),
+                Style::default().fg(text_color),
+            ));
+        }
+        // Zen words come from the typist, not the seed
+        if !zen {
+            spans.push(Span::styled("seed ", Style::default().fg(dim)));
+            spans.push(Span::styled(
+                format!("{}", result.seed),
+                Style::default().fg(stats_color),
+            ));
+        }
+        let line = Line::from(spans);
+        let w = line.width() as u16;
+        let x = chunks[3].x + chunks[3].width.saturating_sub(w) / 2;
+        frame.render_widget(line, Rect::new(x, chunks[3].y + 1, chunks[3].width, 1));
     }
 
-    // WPM Chart
-    frame.render_widget(
-        ResultsChart {
-            wpm_history: &app.result_wpm_history,
-            theme,
-            terminal_width: app.terminal_width,
-            height: 8,
-        },
-        chunks[4],
-    );
+    // WPM Chart, or per-word bursts
+    if app.show_word_history {
+        frame.render_widget(
+            WordHistory {
+                words: &result.word_history,
+                theme,
+                terminal_width: app.terminal_width,
+            },
+            chunks[4],
+        );
+    } else {
+        frame.render_widget(
+            ResultsChart {
+                wpm_history: &app.result_wpm_history,
+                theme,
+                terminal_width: app.terminal_width,
+                height: 8,
+            },
+            chunks[4],
+        );
+    }
 
     // Footer
     {
+        let replay = if zen { "" } else { "r: replay seed  " };
+        let toggle = if app.show_word_history {
+            "h: wpm chart"
+        } else {
+            "h: word history"
+        };
         let footer = Line::from(Span::styled(
-            "tab: restart  esc: menu",
+            format!("tab: restart  {replay}{toggle}  esc: menu"),
             Style::default().fg(dim),
         ));
         let w = footer.width() as u16;
@@ -1,17 +1,17 @@
 use crate::app::App;
-use crate::config::store::get_scores;
 use crate::data::themes::parse_hex;
-use crate::types::{TestMode, ThemeColors};
+use crate::types::{ScoreEntry, ScoreStatus, TestMode, ThemeColors};
 use ratatui::layout::{Constraint, Layout, Rect};
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::Frame;
 
 pub fn render(frame: &mut Frame, area: Rect, app: &App, theme: &ThemeColors) {
-    let scores = get_scores();
+    let scores = filtered_scores(&app.scores, app.scores_language.as_deref());
     let accent = parse_hex(&theme.accent);
     let text_color = parse_hex(&theme.text);
     let dim = parse_hex(&theme.text_dim);
+    let incorrect_color = parse_hex(&theme.incorrect);
 
     let chunks = Layout::vertical([
         Constraint::Length(2), // title
@@ -21,8 +21,12 @@ pub fn render(frame: &mut Frame, area: Rect, app: &App, theme: &ThemeColors) {
     .split(area);
 
     // Title
+    let title = match &app.scores_language {
+        Some(language) => format!("scores · {}", language.replace('_', " ")),
+        None => "scores".to_string(),
+    };
     let title = Line::from(Span::styled(
-        "scores",
+        title,
         Style::default()
             .fg(accent)
             .add_modifier(Modifier::BOLD),
@@ -46,7 +50,13 @@ pub fn render(frame: &mut Frame, area: Rect, app: &App, theme: &ThemeColors) {
             Rect::new(mx, chunks[1].y + 2, chunks[1].width, 1),
         );
     } else {
-        let best_wpm = scores.iter().map(|s| s.wpm).max().unwrap_or(0);
+        // Failed and funbox runs are listed but never count as a best
+        let best_wpm = scores
+            .iter()
+            .filter(|s| s.status == ScoreStatus::Completed && s.funbox.is_none())
+            .map(|s| s.wpm)
+            .max()
+            .unwrap_or(0);
         let visible_rows = chunks[1].height.saturating_sub(2) as usize; // header + separator
         let scroll = app.scores_scroll;
 
@@ -91,15 +101,38 @@ pub fn render(frame: &mut Frame, area: Rect, app: &App, theme: &ThemeColors) {
                 break;
             }
             let idx = scroll + i + 1;
-            let is_best = score.wpm == best_wpm;
-            let wpm_color = if is_best { accent } else { text_color };
+            let failed = score.status == ScoreStatus::Failed;
+            let is_best = !failed && score.funbox.is_none() && score.wpm == best_wpm;
+            let wpm_color = if failed {
+                incorrect_color
+            } else if is_best {
+                accent
+            } else {
+                text_color
+            };
 
             let mode_str = match score.mode {
                 TestMode::Time => format!("time {}s", score.duration),
                 TestMode::Words => format!("words {}w", score.duration),
+                TestMode::Quote => format!("quote {}w", score.duration),
+                TestMode::Custom => format!("custom {}w", score.duration),
+                TestMode::Zen => format!("zen {}s", score.duration),
+                TestMode::Code => format!("code {}w", score.duration),
+            };
+            // Zen has no target text to be accurate against
+            let acc_str = if score.mode == TestMode::Zen {
+                "n/a".to_string()
+            } else {
+                format!("{:.1}%", score.accuracy)
             };
 
-            let date_str = format_date(&score.date);
+            let mut date_str = format_date(&score.date);
+            if let Some(funbox) = score.funbox {
+                date_str = format!("{date_str}  {}", funbox.label());
+            }
+            if failed {
+                date_str = format!("{date_str}  failed");
+            }
 
             let row = Line::from(vec![
                 Span::styled(pad(&idx.to_string(), 4), Style::default().fg(dim)),
@@ -115,12 +148,12 @@ pub fn render(frame: &mut Frame, area: Rect, app: &App, theme: &ThemeColors) {
                 ),
                 Span::styled(" ", Style::default()),
                 Span::styled(
-                    pad(&format!("{:.1}%", score.accuracy), 7),
+                    pad(&acc_str, 7),
                     Style::default().fg(text_color),
                 ),
                 Span::styled(" ", Style::default()),
                 Span::styled(
-                    pad(&score.language.replace('_', " "), 12),
+                    pad(&score.typed_language().replace('_', " "), 12),
                     Style::default().fg(text_color),
                 ),
                 Span::styled(" ", Style::default()),
@@ -155,7 +188,7 @@ pub fn render(frame: &mut Frame, area: Rect, app: &App, theme: &ThemeColors) {
 
     // Footer
     let footer = Line::from(Span::styled(
-        "esc: back to menu",
+        "l: filter by language  esc: back to menu",
         Style::default().fg(dim),
     ));
     let fw = footer.width() as u16;
@@ -166,6 +199,28 @@ pub fn render(frame: &mut Frame, area: Rect, app: &App, theme: &ThemeColors) {
     );
 }
 
+/// `scores`, only those typed in `language` when one is given.
+pub fn filtered_scores<'a>(scores: &'a [ScoreEntry], language: Option<&str>) -> Vec<&'a ScoreEntry> {
+    scores
+        .iter()
+        .filter(|s| language.is_none() || language == Some(s.typed_language()))
+        .collect()
+}
+
+/// The next language filter: each language with scores in turn, then all.
+pub fn next_language_filter(scores: &[ScoreEntry], current: Option<&str>) -> Option<String> {
+    let mut languages: Vec<String> = scores
+        .iter()
+        .map(|s| s.typed_language().to_string())
+        .collect();
+    languages.sort();
+    languages.dedup();
+    match current {
+        None => languages.into_iter().next(),
+        Some(current) => languages.into_iter().find(|l| l.as_str() > current),
+    }
+}
+
 fn pad(s: &str, len: usize) -> String {
     format!("{:<width$}", s, width = len)
 }
@@ -1,7 +1,7 @@
 use crate::app::App;
 use crate::data::themes::parse_hex;
 use crate::engine::timer::{get_elapsed_seconds, get_remaining_seconds};
-use crate::types::ThemeColors;
+use crate::types::{TestMode, ThemeColors, ZenEndKey};
 use crate::ui::stats_bar::StatsBar;
 use crate::ui::word_display::WordDisplay;
 use ratatui::layout::{Constraint, Layout, Rect};
@@ -28,6 +28,7 @@ pub fn render(frame: &mut Frame, area: Rect, app: &App, theme: &ThemeColors) {
             remaining_seconds: get_remaining_seconds(&app.timer),
             phase: app.phase,
             mode: app.config.mode,
+            blind: app.config.blind_mode,
             theme,
         },
         chunks[0],
@@ -42,6 +43,9 @@ pub fn render(frame: &mut Frame, area: Rect, app: &App, theme: &ThemeColors) {
                 cursor_position: input_state.cursor_position,
                 theme,
                 one_line: app.config.one_line,
+                blind: app.config.blind_mode,
+                zen: app.config.mode == TestMode::Zen,
+                nospace: input_state.options.nospace,
             },
             chunks[1],
         );
@@ -50,6 +54,16 @@ pub fn render(frame: &mut Frame, area: Rect, app: &App, theme: &ThemeColors) {
     // Footer
     let footer_text = if app.restart_pending {
         "enter: confirm restart  any key: cancel  esc: menu"
+    } else if app.config.mode == TestMode::Zen {
+        match app.config.zen_end_key {
+            ZenEndKey::Enter => "enter: finish  tab: restart  esc: menu",
+            ZenEndKey::ShiftEnter => "shift+enter: finish  tab: restart  esc: menu",
+            ZenEndKey::CtrlD => "ctrl+d: finish  tab: restart  esc: menu",
+        }
+    } else if app.config.mode == TestMode::Code && !app.config.auto_indent {
+        "enter: new line  tab: indent (restart elsewhere)  esc: menu"
+    } else if app.config.mode == TestMode::Code {
+        "enter: new line  tab: restart  esc: menu"
     } else {
         "tab: restart  esc: menu"
     };
@@ -60,4 +74,14 @@ pub fn render(frame: &mut Frame, area: Rect, app: &App, theme: &ThemeColors) {
         footer,
         Rect::new(fx, chunks[2].y, chunks[2].width, 1),
     );
+
+    if let Some(notice) = &app.notice {
+        let line = Line::from(Span::styled(
+            notice.as_str(),
+            Style::default().fg(parse_hex(&theme.incorrect)),
+        ));
+        let nw = line.width() as u16;
+        let nx = chunks[2].x + chunks[2].width.saturating_sub(nw) / 2;
+        frame.render_widget(line, Rect::new(nx, chunks[2].y + 1, chunks[2].width, 1));
+    }
 }
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::time::Instant;
 
 // ── Character / Word state ──────────────────────────────────────────
@@ -15,6 +16,88 @@ pub struct WordState {
     pub typed: String,
     pub chars: Vec<CharResult>,
     pub completed: bool,
+    /// Test time (ms) when the word became current: the first keystroke for
+    /// the opening word, otherwise the space that finished the previous one.
+    pub started_ms: Option<f64>,
+    /// Test time (ms) of the space that last completed the word.
+    pub completed_ms: Option<f64>,
+    /// Code mode: line breaks after the word (more than one leaves blank
+    /// lines). Enter, not space, moves on from such a word.
+    pub line_breaks: usize,
+    /// Leading indentation that is shown but skipped rather than typed.
+    pub indent: String,
+}
+
+/// Timing of a single completed word.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WordTiming {
+    pub word: String,
+    pub typed: String,
+    pub correct: bool,
+    pub duration_ms: f64,
+    /// Speed over just this word: typed characters / 5 per minute.
+    pub burst_wpm: f64,
+}
+
+// ── Keystroke log ───────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeystrokeKind {
+    Char(char),
+    Space,
+    Enter,
+    Tab,
+    Backspace,
+    CtrlBackspace,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeystrokeEvent {
+    /// Milliseconds since the timer was started.
+    pub timestamp_ms: f64,
+    pub kind: KeystrokeKind,
+    /// Word and char position the keystroke was aimed at.
+    pub word_index: usize,
+    pub char_index: usize,
+    /// `None` for keystrokes that aren't scored, such as deletions.
+    pub correct: Option<bool>,
+}
+
+/// How one expected character fared over a test.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct KeyStats {
+    pub correct: usize,
+    pub incorrect: usize,
+    /// Wrong characters typed where this one was expected, with counts.
+    pub substitutions: BTreeMap<String, usize>,
+    /// Mean milliseconds since the previous keystroke, over correct presses.
+    pub avg_press_ms: f64,
+    /// Number of correct presses `avg_press_ms` was averaged over.
+    pub timed_presses: usize,
+}
+
+impl KeyStats {
+    pub fn presses(&self) -> usize {
+        self.correct + self.incorrect
+    }
+
+    /// Share of presses that were wrong, from 0 to 1.
+    pub fn error_rate(&self) -> f64 {
+        match self.presses() {
+            0 => 0.0,
+            presses => self.incorrect as f64 / presses as f64,
+        }
+    }
+}
+
+/// A key or bigram adaptive practice worked on, with how it had been going
+/// over recent tests.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyTarget {
+    pub key: String,
+    pub error_rate: f64,
+    pub avg_press_ms: f64,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -28,6 +111,10 @@ pub struct StatsSnapshot {
     pub missed_chars: usize,
     #[allow(dead_code)] // used by tests
     pub elapsed_seconds: f64,
+    /// Per expected character, keyed by grapheme.
+    pub key_stats: BTreeMap<String, KeyStats>,
+    /// Per pair of consecutive characters within a word, e.g. `th`.
+    pub bigram_stats: BTreeMap<String, KeyStats>,
 }
 
 // ── Test configuration ──────────────────────────────────────────────
@@ -37,6 +124,123 @@ pub struct StatsSnapshot {
 pub enum TestMode {
     Time,
     Words,
+    Quote,
+    Custom,
+    Zen,
+    Code,
+}
+
+/// Funbox modifiers that rewrite the text (or how it is typed) for fun and
+/// practice. Runs with one are kept apart from plain runs in the scores.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Funbox {
+    /// Every word reversed.
+    Mirror,
+    /// rAnDoM cAsE letters.
+    RandomCase,
+    /// Every word capitalized.
+    Capitals,
+    /// Random letter strings instead of words.
+    Gibberish,
+    /// Digits only.
+    #[serde(rename = "58008")]
+    Numbers,
+    /// Words run together; each one completes without a space.
+    Nospace,
+    /// Letters swapped with their mirror image on a QWERTY keyboard row.
+    LayoutMirror,
+}
+
+impl Funbox {
+    pub fn label(self) -> &'static str {
+        match self {
+            Funbox::Mirror => "mirror",
+            Funbox::RandomCase => "rAnDoM cAsE",
+            Funbox::Capitals => "capitals",
+            Funbox::Gibberish => "gibberish",
+            Funbox::Numbers => "58008",
+            Funbox::Nospace => "nospace",
+            Funbox::LayoutMirror => "layout mirror",
+        }
+    }
+}
+
+/// Key combination that ends a zen mode test.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ZenEndKey {
+    #[default]
+    Enter,
+    ShiftEnter,
+    CtrlD,
+}
+
+/// Where code mode takes its code from.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CodeSource {
+    /// The snippets bundled for each code language.
+    #[default]
+    Snippets,
+    /// Source files under `TestConfig::repo_dir`.
+    Repo,
+}
+
+/// What part of a source file is typed when practicing on a repository.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExcerptKind {
+    /// A whole function, falling back to lines when a file has none that fit.
+    #[default]
+    Functions,
+    /// A run of consecutive lines.
+    Lines,
+}
+
+/// Quote length groups, matching the `groups` ranges of a quote collection.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QuoteLength {
+    Short,
+    #[default]
+    Medium,
+    Long,
+    Thicc,
+}
+
+impl QuoteLength {
+    /// Index into a quote collection's `groups`.
+    pub fn group_index(self) -> usize {
+        match self {
+            QuoteLength::Short => 0,
+            QuoteLength::Medium => 1,
+            QuoteLength::Long => 2,
+            QuoteLength::Thicc => 3,
+        }
+    }
+}
+
+/// MonkeyType's "stop on error": `Letter` rejects wrong keystrokes outright,
+/// `Word` lets mistakes be typed but won't advance past an incorrect word.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StopOnError {
+    #[default]
+    Off,
+    Letter,
+    Word,
+}
+
+/// How much correcting is allowed: `On` keeps backspace within the current
+/// word, `Max` disables it entirely.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Confidence {
+    #[default]
+    Off,
+    On,
+    Max,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -49,7 +253,102 @@ pub struct TestConfig {
     pub one_line: bool,
     pub punctuation: bool,
     pub numbers: bool,
-    pub backspace: bool,
+    #[serde(default)]
+    pub confidence: Confidence,
+    /// The backspace toggle of settings saved before confidence modes; read
+    /// once by `settings_from_json`, never written back.
+    #[serde(default, skip_serializing)]
+    pub backspace: Option<bool>,
+    /// Backspace can reopen completed words that were typed correctly, as it
+    /// always could; turned off, only mistyped words can be reopened.
+    #[serde(default = "default_freedom")]
+    pub freedom: bool,
+    #[serde(default)]
+    pub stop_on_error: StopOnError,
+    #[serde(default)]
+    pub lazy_mode: bool,
+    #[serde(default)]
+    pub blind_mode: bool,
+    /// Fail once live wpm drops below this (after a grace period).
+    #[serde(default)]
+    pub min_wpm: Option<u32>,
+    /// Fail once accuracy (percent) drops below this (after a grace period).
+    #[serde(default)]
+    pub min_accuracy: Option<u32>,
+    /// Fail on the first incorrect keystroke.
+    #[serde(default)]
+    pub sudden_death: bool,
+    /// Fixed word generator seed; `None` picks a fresh one for every test.
+    #[serde(default)]
+    pub seed: Option<u64>,
+    #[serde(default)]
+    pub quote_length: QuoteLength,
+    /// Text typed in custom mode, from a file, stdin or the menu editor.
+    #[serde(default)]
+    pub custom_text: String,
+    #[serde(default)]
+    pub custom_shuffle: bool,
+    /// How many times the custom text is typed back to back.
+    #[serde(default = "default_custom_repeat")]
+    pub custom_repeat: u32,
+    #[serde(default)]
+    pub zen_end_key: ZenEndKey,
+    #[serde(default)]
+    pub funbox: Option<Funbox>,
+    /// Code mode: leading indentation is skipped instead of typed.
+    #[serde(default = "default_auto_indent")]
+    pub auto_indent: bool,
+    #[serde(default)]
+    pub code_source: CodeSource,
+    /// Local directory code mode practices on, e.g. a checked out repository.
+    #[serde(default)]
+    pub repo_dir: Option<String>,
+    #[serde(default)]
+    pub excerpt_kind: ExcerptKind,
+    /// Drop comments from repository excerpts before typing them.
+    #[serde(default)]
+    pub strip_comments: bool,
+    /// Generated words favor the keys and bigrams past tests found weakest.
+    #[serde(default)]
+    pub adaptive: bool,
+    #[serde(default)]
+    pub word_list: WordListSize,
+    #[serde(default)]
+    pub word_filter: WordFilter,
+}
+
+fn default_custom_repeat() -> u32 {
+    1
+}
+
+fn default_auto_indent() -> bool {
+    true
+}
+
+fn default_freedom() -> bool {
+    true
+}
+
+impl TestConfig {
+    /// Lazy mode is dropped for languages flagged `noLazyMode` (e.g. code).
+    pub fn input_options(&self, language: &Language) -> InputOptions {
+        let lazy_mode = self.lazy_mode && !language.no_lazy_mode.unwrap_or(false);
+        InputOptions {
+            stop_on_error: self.stop_on_error,
+            confidence: self.confidence,
+            lock_correct_words: !self.freedom,
+            lazy_mode,
+            additional_accents: if lazy_mode {
+                language.additional_accents.clone()
+            } else {
+                Vec::new()
+            },
+            open_ended: self.mode == TestMode::Zen,
+            nospace: self.funbox == Some(Funbox::Nospace)
+                && !matches!(self.mode, TestMode::Zen | TestMode::Code),
+            skip_indent: self.auto_indent,
+        }
+    }
 }
 
 impl Default for TestConfig {
@@ -63,7 +362,30 @@ impl Default for TestConfig {
             one_line: false,
             punctuation: false,
             numbers: false,
-            backspace: true,
+            confidence: Confidence::Off,
+            backspace: None,
+            freedom: default_freedom(),
+            stop_on_error: StopOnError::Off,
+            lazy_mode: false,
+            blind_mode: false,
+            min_wpm: None,
+            min_accuracy: None,
+            sudden_death: false,
+            seed: None,
+            quote_length: QuoteLength::default(),
+            custom_text: String::new(),
+            custom_shuffle: false,
+            custom_repeat: default_custom_repeat(),
+            zen_end_key: ZenEndKey::default(),
+            funbox: None,
+            auto_indent: default_auto_indent(),
+            code_source: CodeSource::default(),
+            repo_dir: None,
+            excerpt_kind: ExcerptKind::default(),
+            strip_comments: false,
+            adaptive: false,
+            word_list: WordListSize::default(),
+            word_filter: WordFilter::default(),
         }
     }
 }
@@ -75,6 +397,23 @@ pub enum TestPhase {
     Finished,
 }
 
+/// Why a test was ended early by a fail condition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FailReason {
+    MinWpm,
+    MinAccuracy,
+    SuddenDeath,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScoreStatus {
+    #[default]
+    Completed,
+    Failed,
+}
+
 #[derive(Debug, Clone)]
 pub struct TestResult {
     pub wpm: u32,
@@ -88,6 +427,23 @@ pub struct TestResult {
     pub total_words: usize,
     pub correct_words: usize,
     pub elapsed_seconds: f64,
+    pub keystrokes: Vec<KeystrokeEvent>,
+    pub key_stats: BTreeMap<String, KeyStats>,
+    pub bigram_stats: BTreeMap<String, KeyStats>,
+    pub word_history: Vec<WordTiming>,
+    pub failed: Option<FailReason>,
+    /// Seed the words were generated from.
+    pub seed: u64,
+    /// The quote typed in quote mode, for attribution.
+    pub quote: Option<Quote>,
+    /// The snippet typed in code mode, for attribution.
+    pub snippet: Option<Snippet>,
+    /// The piece of a repository file typed in code mode, for attribution.
+    pub excerpt: Option<SourceExcerpt>,
+    /// Language of the code typed in code mode, e.g. `code_python`.
+    pub source_language: Option<String>,
+    /// Weak keys adaptive practice favored, as they stood before the test.
+    pub targets: Vec<KeyTarget>,
     pub config: TestConfig,
 }
 
@@ -101,6 +457,38 @@ pub struct ScoreEntry {
     pub mode: TestMode,
     pub duration: u32,
     pub date: String,
+    #[serde(default)]
+    pub status: ScoreStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fail_reason: Option<FailReason>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quote_id: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub funbox: Option<Funbox>,
+    /// Language of the code typed in code mode, which may differ from the
+    /// selected `language` (e.g. a repository's python files).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_language: Option<String>,
+    /// File in the `keystrokes` folder holding this test's keystroke log.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keystroke_log: Option<String>,
+    /// Only read from score files written before logs got files of their
+    /// own; moved out on the next save.
+    #[serde(default, skip_serializing)]
+    pub keystrokes: Vec<KeystrokeEvent>,
+    #[serde(default)]
+    pub key_stats: BTreeMap<String, KeyStats>,
+    #[serde(default)]
+    pub bigram_stats: BTreeMap<String, KeyStats>,
+}
+
+impl ScoreEntry {
+    /// The language actually typed: the code's language in code mode.
+    pub fn typed_language(&self) -> &str {
+        self.source_language.as_deref().unwrap_or(&self.language)
+    }
 }
 
 // ── Language ────────────────────────────────────────────────────────
@@ -114,18 +502,116 @@ pub struct Language {
     pub ordered_by_frequency: Option<bool>,
     #[serde(default)]
     pub no_lazy_mode: Option<bool>,
+    #[serde(default)]
+    pub additional_accents: Vec<(String, String)>,
+    #[serde(default)]
+    pub punctuation: PunctuationProfile,
     pub words: Vec<String>,
 }
 
+/// How punctuation mode writes a language, read from the `punctuation`
+/// object of its word list. Missing fields fall back to plain English rules.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PunctuationProfile {
+    /// Opening and closing quotation marks, e.g. `„` and `“` in German.
+    pub quotes: (String, String),
+    /// `? ! : ;` and the quotation marks are set off by a space, as in French.
+    pub spaced_marks: bool,
+    /// Questions and exclamations open with `¿` and `¡`, as in Spanish.
+    pub inverted_marks: bool,
+    /// Nouns written with a capital, as in German. Drawn words are spelled
+    /// as listed here and only nouns form hyphenated compounds. Words that
+    /// are also verbs or pronouns, like `leben` or `Sie`, stay out.
+    pub nouns: Vec<String>,
+    /// Occasionally join two words with a hyphen.
+    pub hyphenation: bool,
+    /// Word and contracted form pairs, e.g. `["do", "don't"]` or
+    /// `["ce", "c'est"]`. The form must start with the word, or with the word
+    /// elided before an apostrophe; other pairs are ignored.
+    pub contractions: Vec<(String, String)>,
+}
+
+impl Default for PunctuationProfile {
+    fn default() -> Self {
+        Self {
+            quotes: ("\"".into(), "\"".into()),
+            spaced_marks: false,
+            inverted_marks: false,
+            nouns: Vec::new(),
+            hyphenation: true,
+            contractions: Vec::new(),
+        }
+    }
+}
+
+// ── Quotes ──────────────────────────────────────────────────────────
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Quote {
+    pub text: String,
+    pub source: String,
+    pub id: u32,
+}
+
+impl Quote {
+    /// Length in characters, as used by the collection's `groups`.
+    pub fn length(&self) -> usize {
+        self.text.chars().count()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[allow(dead_code)] // fields deserialized from JSON, used by tests
+pub struct QuoteCollection {
+    pub language: String,
+    /// Inclusive character-length ranges for short, medium, long and thicc.
+    pub groups: Vec<[usize; 2]>,
+    pub quotes: Vec<Quote>,
+}
+
+// ── Code snippets ───────────────────────────────────────────────────
+
+/// A multi-line piece of code typed in code mode, newlines and all.
+#[derive(Debug, Clone, Deserialize)]
+#[allow(dead_code)] // fields deserialized from JSON, used by tests
+pub struct Snippet {
+    pub code: String,
+    pub source: String,
+    pub id: u32,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[allow(dead_code)] // fields deserialized from JSON, used by tests
+pub struct SnippetCollection {
+    pub language: String,
+    pub snippets: Vec<Snippet>,
+}
+
+/// Lines cut from a source file for code mode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceExcerpt {
+    pub code: String,
+    /// Path relative to the repository directory.
+    pub path: String,
+    /// First and last line of the excerpt, counting from 1.
+    pub lines: (usize, usize),
+    /// Code language detected from the file extension, e.g. `code_rust`.
+    pub language: String,
+}
+
 // ── Theme ───────────────────────────────────────────────────────────
 
 #[derive(Debug, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 #[allow(dead_code)] // name deserialized from JSON
 pub struct ThemeColors {
+    /// Theme files are named after the file instead.
+    #[serde(default)]
     pub name: String,
     pub bg: String,
     pub text: String,
+    #[serde(alias = "text_dim")]
     pub text_dim: String,
     pub correct: String,
     pub incorrect: String,
@@ -135,8 +621,61 @@ pub struct ThemeColors {
     pub stats: String,
 }
 
+impl ThemeColors {
+    /// Every color with its name in theme files.
+    pub fn colors(&self) -> [(&'static str, &str); 9] {
+        [
+            ("bg", &self.bg),
+            ("text", &self.text),
+            ("textDim", &self.text_dim),
+            ("correct", &self.correct),
+            ("incorrect", &self.incorrect),
+            ("extra", &self.extra),
+            ("cursor", &self.cursor),
+            ("accent", &self.accent),
+            ("stats", &self.stats),
+        ]
+    }
+
+    /// The colors of [`ThemeColors::colors`], in the same order, to edit.
+    pub fn colors_mut(&mut self) -> [&mut String; 9] {
+        [
+            &mut self.bg,
+            &mut self.text,
+            &mut self.text_dim,
+            &mut self.correct,
+            &mut self.incorrect,
+            &mut self.extra,
+            &mut self.cursor,
+            &mut self.accent,
+            &mut self.stats,
+        ]
+    }
+}
+
 // ── Input state ─────────────────────────────────────────────────────
 
+/// Typing rules enforced by the input processor.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InputOptions {
+    pub stop_on_error: StopOnError,
+    pub confidence: Confidence,
+    /// Keep completed words that were typed correctly closed to backspace.
+    /// Freedom mode turns this off.
+    pub lock_correct_words: bool,
+    /// Accept unaccented spellings such as `e` for `é` or `ss` for `ß`.
+    pub lazy_mode: bool,
+    /// Language-specific lazy spellings that override the built-in ones.
+    pub additional_accents: Vec<(String, String)>,
+    /// Zen mode: there is no target text, whatever is typed becomes the words
+    /// and the list grows as the typist goes.
+    pub open_ended: bool,
+    /// Nospace funbox: a word completes as soon as it is typed correctly.
+    pub nospace: bool,
+    /// Leading indentation of a line is shown but not typed.
+    pub skip_indent: bool,
+}
+
 #[derive(Debug, Clone)]
 pub struct InputState {
     pub words: Vec<WordState>,
@@ -146,6 +685,10 @@ pub struct InputState {
     pub historical_error_chars: usize,
     pub keypress_correct: usize,
     pub keypress_incorrect: usize,
+    /// Wrong characters that stop-on-error refused to insert.
+    pub rejected_chars: usize,
+    pub keystrokes: Vec<KeystrokeEvent>,
+    pub options: InputOptions,
 }
 
 // ── Timer state ─────────────────────────────────────────────────────
@@ -167,6 +710,138 @@ pub struct GeneratorConfig {
     pub count: usize,
     pub punctuation: bool,
     pub numbers: bool,
+    /// Same seed, same words and punctuation. `None` draws from the thread rng.
+    pub seed: Option<u64>,
+    /// Keys and bigrams to work on: words containing them are drawn more
+    /// often. Empty for plain frequency-based sampling.
+    pub targets: Vec<String>,
+    /// Which of the language's word lists to draw from.
+    pub list_size: WordListSize,
+    /// Narrows the word list words are drawn from.
+    pub filter: WordFilter,
+}
+
+/// Word lists come in sizes: the language's own list (about 200 words) and
+/// bigger variants named like `english_1k`, holding the most frequent words.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WordListSize {
+    #[default]
+    #[serde(rename = "200")]
+    Base,
+    #[serde(rename = "1k")]
+    OneK,
+    #[serde(rename = "5k")]
+    FiveK,
+    #[serde(rename = "10k")]
+    TenK,
+}
+
+impl WordListSize {
+    pub const ALL: [WordListSize; 4] = [
+        WordListSize::Base,
+        WordListSize::OneK,
+        WordListSize::FiveK,
+        WordListSize::TenK,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            WordListSize::Base => "200",
+            WordListSize::OneK => "1k",
+            WordListSize::FiveK => "5k",
+            WordListSize::TenK => "10k",
+        }
+    }
+
+    /// Suffix of the variant's name, e.g. `_1k` in `english_1k`.
+    pub fn suffix(self) -> &'static str {
+        match self {
+            WordListSize::Base => "",
+            WordListSize::OneK => "_1k",
+            WordListSize::FiveK => "_5k",
+            WordListSize::TenK => "_10k",
+        }
+    }
+}
+
+/// Restricts generated words for focused drills. Letters are compared
+/// case-insensitively; lengths count characters.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WordFilter {
+    pub min_length: Option<usize>,
+    pub max_length: Option<usize>,
+    /// Every word contains at least one of these characters; empty requires none.
+    pub required: String,
+    /// Every character of a word is on these keys; `None` allows any.
+    pub allowed: Option<KeySet>,
+    /// Only list positions `from..to`, counting from the most frequent word
+    /// at 0. Needs a list ordered by frequency.
+    pub ranks: Option<(usize, usize)>,
+    /// Layout the allowed keys are read from.
+    pub layout: KeyboardLayout,
+}
+
+impl WordFilter {
+    pub fn is_empty(&self) -> bool {
+        self.min_length.is_none()
+            && self.max_length.is_none()
+            && self.required.is_empty()
+            && self.allowed.is_none()
+            && self.ranks.is_none()
+    }
+}
+
+/// Groups of keys a drill can be limited to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeySet {
+    HomeRow,
+    TopRow,
+    BottomRow,
+    LeftHand,
+    RightHand,
+}
+
+impl KeySet {
+    pub fn label(self) -> &'static str {
+        match self {
+            KeySet::HomeRow => "home row",
+            KeySet::TopRow => "top row",
+            KeySet::BottomRow => "bottom row",
+            KeySet::LeftHand => "left hand",
+            KeySet::RightHand => "right hand",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeyboardLayout {
+    #[default]
+    Qwerty,
+    Dvorak,
+    Colemak,
+}
+
+/// A word filter constraint dropped because too few words met it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Relaxation {
+    Length,
+    Required,
+    Ranks,
+    Allowed,
+}
+
+impl Relaxation {
+    pub fn label(self) -> &'static str {
+        match self {
+            Relaxation::Length => "word length",
+            Relaxation::Required => "required characters",
+            Relaxation::Ranks => "frequency window",
+            Relaxation::Allowed => "allowed keys",
+        }
+    }
 }
 
 // ── Screen enum ─────────────────────────────────────────────────────
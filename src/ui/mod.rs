@@ -2,4 +2,7 @@ pub mod header;
 pub mod picker;
 pub mod results_chart;
 pub mod stats_bar;
+pub mod text_editor;
+pub mod theme_editor;
 pub mod word_display;
+pub mod word_history;
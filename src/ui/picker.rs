@@ -24,6 +24,8 @@ pub enum PickerResult {
     Cancelled,
     /// Highlighted item changed (for theme preview)
     Highlighted(String),
+    /// User asked to edit the highlighted item (themes)
+    Edit(String),
     /// Still active, consumed the key
     Active,
 }
@@ -75,6 +77,11 @@ impl PickerState {
                 }
                 return PickerResult::Cancelled;
             }
+            KeyCode::Tab => {
+                if let Some(h) = self.current_highlighted() {
+                    return PickerResult::Edit(h.to_string());
+                }
+            }
             KeyCode::Up => {
                 self.cursor = self.cursor.saturating_sub(1);
                 if let Some(h) = self.current_highlighted() {
@@ -97,14 +104,12 @@ impl PickerState {
                     return PickerResult::Highlighted(h.to_string());
                 }
             }
-            KeyCode::Char(c) => {
-                if !key.modifiers.contains(KeyModifiers::CONTROL) {
-                    self.search.push(c);
-                    self.cursor = 0;
-                    self.refilter();
-                    if let Some(h) = self.current_highlighted() {
-                        return PickerResult::Highlighted(h.to_string());
-                    }
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.search.push(c);
+                self.cursor = 0;
+                self.refilter();
+                if let Some(h) = self.current_highlighted() {
+                    return PickerResult::Highlighted(h.to_string());
                 }
             }
             _ => {}
@@ -116,6 +121,8 @@ impl PickerState {
 pub struct Picker<'a> {
     pub state: &'a PickerState,
     pub theme: &'a ThemeColors,
+    /// Items can be opened for editing with Tab, e.g. themes.
+    pub editable: bool,
 }
 
 impl Widget for Picker<'_> {
@@ -241,7 +248,11 @@ impl Widget for Picker<'_> {
         if y + 1 < inner.y + inner.height {
             y += 1;
             let footer = Line::from(Span::styled(
-                "type to search  enter: select  esc: cancel",
+                if self.editable {
+                    "type to search  enter: select  tab: edit  esc: cancel"
+                } else {
+                    "type to search  enter: select  esc: cancel"
+                },
                 Style::default().fg(dim),
             ));
             buf.set_line(inner.x + 1, y, &footer, inner.width);
@@ -17,7 +17,7 @@ impl Default for ResultsChart<'_> {
     fn default() -> Self {
         Self {
             wpm_history: &[],
-            theme: &crate::data::themes::get_theme("dark"),
+            theme: crate::data::themes::bundled_theme("dark").expect("dark is bundled"),
             terminal_width: 50,
             height: 8,
         }
@@ -74,8 +74,8 @@ impl Widget for ResultsChart<'_> {
                 break;
             }
             let mut line_str = String::with_capacity(width);
-            for col in 0..samples.len() {
-                let normalized = samples[col] / max_wpm;
+            for sample in &samples {
+                let normalized = sample / max_wpm;
                 let bar_height = normalized * height as f64;
 
                 if bar_height >= (row + 1) as f64 {
@@ -13,6 +13,8 @@ pub struct StatsBar<'a> {
     pub remaining_seconds: f64,
     pub phase: TestPhase,
     pub mode: TestMode,
+    /// Hide the live wpm and accuracy, which would give mistakes away.
+    pub blind: bool,
     pub theme: &'a ThemeColors,
 }
 
@@ -38,22 +40,42 @@ impl Widget for StatsBar<'_> {
 
         let time_display = match self.mode {
             TestMode::Time => format!("{}s", self.remaining_seconds.ceil() as u32),
-            TestMode::Words => format!("{}s", self.elapsed_seconds.round() as u32),
+            TestMode::Words
+            | TestMode::Quote
+            | TestMode::Custom
+            | TestMode::Zen
+            | TestMode::Code => {
+                format!("{}s", self.elapsed_seconds.round() as u32)
+            }
         };
 
-        let spans = vec![
-            Span::styled(
-                format!("{}", self.wpm.round() as u32),
-                Style::default().fg(accent),
-            ),
-            Span::styled(" wpm   ", Style::default().fg(stats_color)),
-            Span::styled(
-                format!("{:.1}%", self.accuracy),
-                Style::default().fg(accent),
-            ),
-            Span::styled(" acc   ", Style::default().fg(stats_color)),
-            Span::styled(time_display, Style::default().fg(accent)),
-        ];
+        let spans = if self.blind {
+            vec![Span::styled(time_display, Style::default().fg(accent))]
+        } else if self.mode == TestMode::Zen {
+            // Nothing to be accurate against
+            vec![
+                Span::styled(
+                    format!("{}", self.wpm.round() as u32),
+                    Style::default().fg(accent),
+                ),
+                Span::styled(" wpm   ", Style::default().fg(stats_color)),
+                Span::styled(time_display, Style::default().fg(accent)),
+            ]
+        } else {
+            vec![
+                Span::styled(
+                    format!("{}", self.wpm.round() as u32),
+                    Style::default().fg(accent),
+                ),
+                Span::styled(" wpm   ", Style::default().fg(stats_color)),
+                Span::styled(
+                    format!("{:.1}%", self.accuracy),
+                    Style::default().fg(accent),
+                ),
+                Span::styled(" acc   ", Style::default().fg(stats_color)),
+                Span::styled(time_display, Style::default().fg(accent)),
+            ]
+        };
 
         let line = Line::from(spans);
         let width: usize = line.width();
@@ -0,0 +1,120 @@
+use crate::data::themes::parse_hex;
+use crate::engine::graphemes::{grapheme_width, graphemes, pop_grapheme};
+use crate::types::ThemeColors;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Widget};
+
+const OVERHEAD_ROWS: u16 = 2; // blank + footer
+
+pub struct TextEditorState {
+    pub text: String,
+}
+
+pub enum TextEditorResult {
+    /// User saved the text
+    Saved(String),
+    /// User cancelled
+    Cancelled,
+    /// Still active, consumed the key
+    Active,
+}
+
+impl TextEditorState {
+    pub fn new(text: String) -> Self {
+        Self { text }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> TextEditorResult {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => return TextEditorResult::Cancelled,
+            KeyCode::Enter => return TextEditorResult::Saved(self.text.clone()),
+            KeyCode::Backspace => {
+                pop_grapheme(&mut self.text);
+            }
+            KeyCode::Char('u') if ctrl => self.text.clear(),
+            KeyCode::Char(c) if !ctrl => self.text.push(c),
+            _ => {}
+        }
+        TextEditorResult::Active
+    }
+
+    /// Pasted text keeps its line breaks; Enter alone would save instead.
+    pub fn paste(&mut self, text: &str) {
+        self.text.push_str(&text.replace("\r\n", "\n").replace('\r', "\n"));
+    }
+}
+
+pub struct TextEditor<'a> {
+    pub state: &'a TextEditorState,
+    pub theme: &'a ThemeColors,
+    pub title: &'a str,
+    /// Show how many words the text has, e.g. for custom text.
+    pub word_count: bool,
+}
+
+impl Widget for TextEditor<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let accent = parse_hex(&self.theme.accent);
+        let text_color = parse_hex(&self.theme.text);
+        let dim = parse_hex(&self.theme.text_dim);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(accent))
+            .title(format!(" {} ", self.title));
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        if inner.height <= OVERHEAD_ROWS || inner.width < 2 {
+            return;
+        }
+
+        // Wrap by display width; the tail stays in view while typing
+        let width = (inner.width - 1) as usize;
+        let mut rows: Vec<String> = Vec::new();
+        for line in self.state.text.split('\n') {
+            let mut row = String::new();
+            let mut row_width = 0;
+            for g in graphemes(line) {
+                let w = grapheme_width(g);
+                if row_width + w > width {
+                    rows.push(std::mem::take(&mut row));
+                    row_width = 0;
+                }
+                row.push_str(g);
+                row_width += w;
+            }
+            rows.push(row);
+        }
+
+        let visible_rows = (inner.height - OVERHEAD_ROWS) as usize;
+        let skip = rows.len().saturating_sub(visible_rows);
+        let last = rows.len() - 1;
+        for (i, row) in rows.iter().enumerate().skip(skip) {
+            let mut spans = vec![Span::styled(row.as_str(), Style::default().fg(text_color))];
+            if i == last {
+                spans.push(Span::styled("_", Style::default().fg(accent)));
+            }
+            let y = inner.y + (i - skip) as u16;
+            buf.set_line(inner.x + 1, y, &Line::from(spans), inner.width - 1);
+        }
+
+        // Footer
+        let mut spans = Vec::new();
+        if self.word_count {
+            let words = self.state.text.split_whitespace().count();
+            spans.push(Span::styled(format!("{words} words   "), Style::default().fg(text_color)));
+        }
+        spans.push(Span::styled(
+            "type or paste  enter: save  ctrl+u: clear  esc: cancel",
+            Style::default().fg(dim),
+        ));
+        let footer = Line::from(spans);
+        buf.set_line(inner.x + 1, inner.y + inner.height - 1, &footer, inner.width - 1);
+    }
+}
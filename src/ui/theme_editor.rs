@@ -0,0 +1,296 @@
+use crate::data::themes::{parse_color, parse_hex};
+use crate::engine::input_processor::{create_input_state, process_keystroke};
+use crate::types::{InputState, ThemeColors};
+use crate::ui::results_chart::ResultsChart;
+use crate::ui::word_display::WordDisplay;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Widget};
+
+/// Width of the field list and sliders, left of the preview.
+const FIELDS_WIDTH: u16 = 36;
+const SLIDER_WIDTH: usize = 16;
+/// Shift+Left/Right move a slider this far.
+const COARSE_STEP: u8 = 16;
+const CHANNELS: [&str; 3] = ["r", "g", "b"];
+
+const SAMPLE_TEXT: &str = "the quick brown fox jumps over the lazy dog";
+/// Typed into the sample: a correct word, a mistyped one, one with an extra
+/// letter, and a word in progress.
+const SAMPLE_TYPED: &str = "the quikc brownn fo";
+const SAMPLE_WPM: &[f64] = &[
+    58.0, 66.0, 71.0, 69.0, 76.0, 81.0, 74.0, 83.0, 88.0, 85.0, 91.0, 86.0,
+];
+
+pub struct ThemeEditorState {
+    /// The theme being edited; the preview follows every change.
+    pub theme: ThemeColors,
+    /// Name the theme is saved as.
+    pub name: String,
+    /// 0 is the name, then the colors of [`ThemeColors::colors`].
+    pub row: usize,
+    /// Slider (red, green or blue) the arrow keys move.
+    pub channel: usize,
+    /// Why the last save failed.
+    pub error: Option<String>,
+    sample: InputState,
+}
+
+pub enum ThemeEditorResult {
+    /// User asked to save the theme as `name`
+    Save,
+    /// User cancelled
+    Cancelled,
+    /// Still active, consumed the key
+    Active,
+}
+
+impl ThemeEditorState {
+    /// Starts from a copy of `theme`, to be saved under a name of its own.
+    pub fn new(theme: &ThemeColors) -> Self {
+        let name = if theme.name.ends_with("_custom") {
+            theme.name.clone()
+        } else {
+            format!("{}_custom", theme.name)
+        };
+        let words: Vec<String> = SAMPLE_TEXT.split(' ').map(String::from).collect();
+        let mut sample = create_input_state(&words);
+        for (i, c) in SAMPLE_TYPED.chars().enumerate() {
+            sample = process_keystroke(sample, &c.to_string(), false, false, i as f64 * 150.0);
+        }
+        Self {
+            theme: theme.clone(),
+            name,
+            row: 1,
+            channel: 0,
+            error: None,
+            sample,
+        }
+    }
+
+    fn color(&mut self) -> Option<&mut String> {
+        let row = self.row.checked_sub(1)?;
+        self.theme.colors_mut().into_iter().nth(row)
+    }
+
+    /// Moves the selected slider by `delta`; an unfinished hex value starts
+    /// from black.
+    fn slide(&mut self, delta: i16) {
+        let channel = self.channel;
+        let Some(color) = self.color() else {
+            return;
+        };
+        let mut rgb = match parse_color(color) {
+            Ok(Color::Rgb(r, g, b)) => [r, g, b],
+            _ => [0, 0, 0],
+        };
+        rgb[channel] = (i16::from(rgb[channel]) + delta).clamp(0, 255) as u8;
+        *color = format!("#{:02x}{:02x}{:02x}", rgb[0], rgb[1], rgb[2]);
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> ThemeEditorResult {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let step = if key.modifiers.contains(KeyModifiers::SHIFT) { COARSE_STEP } else { 1 };
+        match key.code {
+            KeyCode::Esc => return ThemeEditorResult::Cancelled,
+            KeyCode::Enter => return ThemeEditorResult::Save,
+            KeyCode::Up => self.row = self.row.saturating_sub(1),
+            KeyCode::Down => self.row = (self.row + 1).min(self.theme.colors().len()),
+            KeyCode::Tab => self.channel = (self.channel + 1) % CHANNELS.len(),
+            KeyCode::BackTab => self.channel = (self.channel + CHANNELS.len() - 1) % CHANNELS.len(),
+            KeyCode::Left => self.slide(-i16::from(step)),
+            KeyCode::Right => self.slide(i16::from(step)),
+            KeyCode::Backspace if self.row == 0 => {
+                self.name.pop();
+            }
+            KeyCode::Char(c) if self.row == 0 && !ctrl => self.name.push(c),
+            KeyCode::Backspace => {
+                if let Some(color) = self.color() {
+                    color.pop();
+                }
+            }
+            // Typing a hex value: `#` starts over, digits fill in #rrggbb
+            KeyCode::Char('#') if !ctrl => {
+                if let Some(color) = self.color() {
+                    *color = "#".into();
+                }
+            }
+            KeyCode::Char(c) if c.is_ascii_hexdigit() && !ctrl => {
+                if let Some(color) = self.color() {
+                    if !color.starts_with('#') || color.len() > 6 {
+                        *color = "#".into();
+                    }
+                    color.push(c.to_ascii_lowercase());
+                }
+            }
+            _ => {}
+        }
+        ThemeEditorResult::Active
+    }
+}
+
+pub struct ThemeEditor<'a> {
+    pub state: &'a ThemeEditorState,
+    /// Theme of the editor itself; the preview uses the edited one.
+    pub theme: &'a ThemeColors,
+}
+
+impl Widget for ThemeEditor<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let accent = parse_hex(&self.theme.accent);
+        let text_color = parse_hex(&self.theme.text);
+        let dim = parse_hex(&self.theme.text_dim);
+        let incorrect = parse_hex(&self.theme.incorrect);
+        let state = self.state;
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(accent))
+            .title(" theme editor ");
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        if inner.height < 4 || inner.width <= FIELDS_WIDTH {
+            return;
+        }
+        let bottom = inner.y + inner.height - 1;
+        let row_style = |selected: bool| {
+            if selected {
+                Style::default().fg(accent).add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(dim)
+            }
+        };
+        let prefix = |selected: bool| if selected { " > " } else { "   " };
+
+        // Name, then every color with a swatch
+        let mut y = inner.y;
+        let mut name = vec![
+            Span::styled(prefix(state.row == 0), row_style(state.row == 0)),
+            Span::styled("name: ", row_style(state.row == 0)),
+            Span::styled(state.name.as_str(), Style::default().fg(text_color)),
+        ];
+        if state.row == 0 {
+            name.push(Span::styled("_", Style::default().fg(accent)));
+        }
+        buf.set_line(inner.x, y, &Line::from(name), FIELDS_WIDTH);
+        y += 2;
+        for (i, (field, value)) in state.theme.colors().iter().enumerate() {
+            if y >= bottom {
+                break;
+            }
+            let selected = state.row == i + 1;
+            let valid = parse_color(value).is_ok();
+            let line = Line::from(vec![
+                Span::styled(prefix(selected), row_style(selected)),
+                Span::styled(format!("{field:<10}"), row_style(selected)),
+                Span::styled(
+                    format!("{value:<10}"),
+                    Style::default().fg(if valid { text_color } else { incorrect }),
+                ),
+                Span::styled("██", Style::default().fg(parse_hex(value))),
+            ]);
+            buf.set_line(inner.x, y, &line, FIELDS_WIDTH);
+            y += 1;
+        }
+
+        // Sliders of the selected color
+        y += 1;
+        let selected = state.row.checked_sub(1).and_then(|i| state.theme.colors().get(i).copied());
+        if let Some((_, value)) = selected {
+            let rgb = match parse_color(value) {
+                Ok(Color::Rgb(r, g, b)) => [r, g, b],
+                _ => [0, 0, 0],
+            };
+            for (c, label) in CHANNELS.iter().enumerate() {
+                if y >= bottom {
+                    break;
+                }
+                let knob = usize::from(rgb[c]) * (SLIDER_WIDTH - 1) / 255;
+                let track: String = (0..SLIDER_WIDTH)
+                    .map(|i| match i.cmp(&knob) {
+                        std::cmp::Ordering::Less => '━',
+                        std::cmp::Ordering::Equal => '●',
+                        std::cmp::Ordering::Greater => '─',
+                    })
+                    .collect();
+                let selected = c == state.channel;
+                let line = Line::from(vec![
+                    Span::styled(format!("   {label} "), row_style(selected)),
+                    Span::styled(track, Style::default().fg(if selected { accent } else { dim })),
+                    Span::styled(format!(" {:>3}", rgb[c]), Style::default().fg(text_color)),
+                ]);
+                buf.set_line(inner.x, y, &line, FIELDS_WIDTH);
+                y += 1;
+            }
+        }
+        if let Some(error) = &state.error {
+            let line =
+                Line::from(Span::styled(format!("   {error}"), Style::default().fg(incorrect)));
+            buf.set_line(inner.x, bottom.saturating_sub(1), &line, inner.width);
+        }
+
+        // Preview on the edited theme's own background
+        let preview = Rect::new(
+            inner.x + FIELDS_WIDTH,
+            inner.y,
+            inner.width - FIELDS_WIDTH,
+            inner.height.saturating_sub(2),
+        );
+        render_preview(&state.theme, &state.sample, preview, buf);
+
+        let footer = Line::from(Span::styled(
+            "hex: type  ←→ (shift ×16): slide  tab: r/g/b  enter: save  esc: back",
+            Style::default().fg(dim),
+        ));
+        buf.set_line(inner.x + 1, bottom, &footer, inner.width - 1);
+    }
+}
+
+/// A sample test and results chart in `theme`.
+fn render_preview(theme: &ThemeColors, sample: &InputState, area: Rect, buf: &mut Buffer) {
+    buf.set_style(area, Style::default().bg(parse_hex(&theme.bg)));
+    if area.height < 6 {
+        return;
+    }
+    let words = Rect::new(area.x, area.y + 1, area.width, 3);
+    WordDisplay {
+        words: &sample.words,
+        current_word_index: sample.current_word_index,
+        cursor_position: sample.cursor_position,
+        theme,
+        one_line: false,
+        blind: false,
+        zen: false,
+        nospace: false,
+    }
+    .render(words, buf);
+
+    let stats = Line::from(vec![
+        Span::styled(
+            "86",
+            Style::default().fg(parse_hex(&theme.accent)).add_modifier(Modifier::BOLD),
+        ),
+        Span::styled(" wpm   ", Style::default().fg(parse_hex(&theme.stats))),
+        Span::styled("94%", Style::default().fg(parse_hex(&theme.text))),
+        Span::styled(" acc", Style::default().fg(parse_hex(&theme.stats))),
+    ]);
+    let x = area.x + area.width.saturating_sub(stats.width() as u16) / 2;
+    buf.set_line(x, area.y + 5, &stats, area.width);
+
+    // Title, separator and stats line around the bars
+    let chart_top = area.y + 7;
+    let chart_rows = (area.y + area.height).saturating_sub(chart_top);
+    if chart_rows >= 5 {
+        ResultsChart {
+            wpm_history: SAMPLE_WPM,
+            theme,
+            terminal_width: area.width.saturating_sub(2) + 10,
+            height: chart_rows - 3,
+        }
+        .render(Rect::new(area.x, chart_top, area.width, chart_rows), buf);
+    }
+}
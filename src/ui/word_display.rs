@@ -1,8 +1,10 @@
+use crate::data::palette::{ColorSupport, color_support};
 use crate::data::themes::parse_hex;
+use crate::engine::graphemes::{display_width, grapheme_width, graphemes};
 use crate::types::{ThemeColors, WordState};
 use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
-use ratatui::style::{Modifier, Style};
+use ratatui::style::{Color, Modifier, Style};
 use ratatui::widgets::Widget;
 
 pub struct WordDisplay<'a> {
@@ -11,6 +13,12 @@ pub struct WordDisplay<'a> {
     pub cursor_position: usize,
     pub theme: &'a ThemeColors,
     pub one_line: bool,
+    /// Render every typed character as correct and hide extras.
+    pub blind: bool,
+    /// Zen mode: the words are just what was typed, shown as plain text.
+    pub zen: bool,
+    /// Nospace funbox: words run together without a gap.
+    pub nospace: bool,
 }
 
 struct LineRange {
@@ -18,13 +26,18 @@ struct LineRange {
     end_index: usize,
 }
 
-fn compute_lines(words: &[WordState], max_width: usize) -> Vec<LineRange> {
+fn compute_lines(
+    words: &[WordState],
+    max_width: usize,
+    blind: bool,
+    gap: usize,
+) -> Vec<LineRange> {
     let mut lines = Vec::new();
     let mut line_start = 0;
     let mut line_width: usize = 0;
 
-    for i in 0..words.len() {
-        let word_len = words[i].word.len() + 1; // +1 for space
+    for (i, word) in words.iter().enumerate() {
+        let word_len = word_display_width(word, blind) + gap; // gap for the space
 
         if line_width + word_len > max_width && line_width > 0 {
             lines.push(LineRange {
@@ -35,6 +48,22 @@ fn compute_lines(words: &[WordState], max_width: usize) -> Vec<LineRange> {
             line_width = 0;
         }
         line_width += word_len;
+
+        // Code keeps its own line breaks, blank lines included
+        if word.line_breaks > 0 {
+            lines.push(LineRange {
+                start_index: line_start,
+                end_index: i + 1,
+            });
+            for _ in 1..word.line_breaks {
+                lines.push(LineRange {
+                    start_index: i + 1,
+                    end_index: i + 1,
+                });
+            }
+            line_start = i + 1;
+            line_width = 0;
+        }
     }
 
     if line_start < words.len() {
@@ -58,19 +87,19 @@ fn compute_tape_range(
     current_word_index: usize,
     cursor_position: usize,
     max_width: usize,
+    blind: bool,
+    gap: usize,
 ) -> TapeRange {
     let anchor = (max_width as f64 * 0.50).floor() as usize;
 
-    let current_word_len = words
+    let display_len = words
         .get(current_word_index)
-        .map(|w| w.word.len())
+        .map(|w| word_display_width(w, blind))
         .unwrap_or(0);
-    let typed_len = words
+    let cursor_col = words
         .get(current_word_index)
-        .map(|w| w.typed.len())
+        .map(|w| cursor_column(w, cursor_position, blind))
         .unwrap_or(0);
-    let display_len = current_word_len.max(typed_len);
-    let cursor_col = cursor_position.min(display_len);
 
     // Fill LEFT of anchor with past words
     let mut left_budget = anchor.saturating_sub(cursor_col);
@@ -81,7 +110,7 @@ fn compute_tape_range(
             break;
         }
         let w = &words[i];
-        let w_len = w.word.len().max(w.typed.len()) + 1;
+        let w_len = word_display_width(w, blind) + gap;
         if w_len > left_budget {
             break;
         }
@@ -93,14 +122,14 @@ fn compute_tape_range(
 
     // Fill RIGHT of anchor with upcoming words
     let right_anchor = max_width - anchor + cursor_col;
-    let mut right_budget = right_anchor.saturating_sub(display_len + 1);
+    let mut right_budget = right_anchor.saturating_sub(display_len + gap);
 
     let mut end_index = current_word_index + 1;
-    for i in (current_word_index + 1)..words.len() {
+    for (i, w) in words.iter().enumerate().skip(current_word_index + 1) {
         if right_budget == 0 {
             break;
         }
-        let w_len = words[i].word.len() + 1;
+        let w_len = word_display_width(w, blind) + gap;
         if w_len > right_budget {
             break;
         }
@@ -115,6 +144,35 @@ fn compute_tape_range(
     }
 }
 
+/// Columns a word occupies on screen: skipped indentation, its target
+/// graphemes, then any extra graphemes typed past the end (hidden in blind mode).
+fn word_display_width(w: &WordState, blind: bool) -> usize {
+    if blind {
+        return display_width(&w.indent) + display_width(&w.word);
+    }
+    let word = graphemes(&w.word);
+    let extra: usize = graphemes(&w.typed)
+        .iter()
+        .skip(word.len())
+        .map(|g| grapheme_width(g))
+        .sum();
+    display_width(&w.indent) + display_width(&w.word) + extra
+}
+
+/// Column of the cursor within a word, given its position in graphemes.
+fn cursor_column(w: &WordState, cursor_position: usize, blind: bool) -> usize {
+    let word = graphemes(&w.word);
+    let typed = graphemes(&w.typed);
+    let extra = if blind { &[][..] } else { &typed[word.len().min(typed.len())..] };
+    display_width(&w.indent)
+        + word
+            .iter()
+            .chain(extra.iter())
+            .take(cursor_position)
+            .map(|g| grapheme_width(g))
+            .sum::<usize>()
+}
+
 impl Widget for WordDisplay<'_> {
     fn render(self, area: Rect, buf: &mut Buffer) {
         if area.height == 0 || area.width < 4 {
@@ -129,14 +187,27 @@ impl Widget for WordDisplay<'_> {
         let dim_color = parse_hex(&self.theme.text_dim);
         let cursor_color = parse_hex(&self.theme.cursor);
 
-        let correct_style = Style::default().fg(correct_color);
-        let incorrect_style = Style::default().fg(incorrect_color);
-        let extra_style = Style::default().fg(extra_color);
-        let dim_style = Style::default().fg(dim_color);
+        // Without colors, modifiers tell typed, mistyped and extra characters
+        // apart; the cursor is reversed either way
+        let mono = color_support() == ColorSupport::Monochrome;
+        let marked = |color: Color, modifier: Modifier| {
+            let style = Style::default().fg(color);
+            if mono { style.add_modifier(modifier) } else { style }
+        };
+        let correct_style = if self.zen {
+            Style::default().fg(parse_hex(&self.theme.text))
+        } else {
+            marked(correct_color, Modifier::BOLD)
+        };
+        let incorrect_style = marked(incorrect_color, Modifier::UNDERLINED);
+        let extra_style = marked(extra_color, Modifier::BOLD | Modifier::UNDERLINED);
+        let dim_style = marked(dim_color, Modifier::DIM);
         let cursor_style = Style::default().fg(cursor_color).add_modifier(Modifier::REVERSED);
-        let extra_cursor_style = Style::default().fg(extra_color).add_modifier(Modifier::REVERSED);
+        let extra_cursor_style =
+            marked(extra_color, Modifier::BOLD | Modifier::UNDERLINED).add_modifier(Modifier::REVERSED);
 
         let padding_x = 2u16;
+        let gap = usize::from(!self.nospace);
 
         if self.one_line {
             let tape = compute_tape_range(
@@ -144,6 +215,8 @@ impl Widget for WordDisplay<'_> {
                 self.current_word_index,
                 self.cursor_position,
                 max_width,
+                self.blind,
+                gap,
             );
             let y = area.y;
             let mut x = area.x + padding_x + tape.leading_pad as u16;
@@ -161,6 +234,8 @@ impl Widget for WordDisplay<'_> {
                     &self.words[word_idx],
                     is_current,
                     self.cursor_position,
+                    self.blind,
+                    self.nospace,
                     correct_style,
                     incorrect_style,
                     extra_style,
@@ -172,7 +247,7 @@ impl Widget for WordDisplay<'_> {
             return;
         }
 
-        let lines = compute_lines(self.words, max_width);
+        let lines = compute_lines(self.words, max_width, self.blind, gap);
 
         // Find which line the current word is on
         let mut current_line = 0;
@@ -185,7 +260,13 @@ impl Widget for WordDisplay<'_> {
             }
         }
 
-        let visible_line_count = 3usize;
+        // Code shows as much of the snippet as fits, prose three lines
+        let code = self.words.iter().any(|w| w.line_breaks > 0);
+        let visible_line_count = if code {
+            (area.height as usize).clamp(3, 12)
+        } else {
+            3
+        };
         let start_line = current_line.saturating_sub(1);
 
         for row in 0..visible_line_count {
@@ -212,6 +293,8 @@ impl Widget for WordDisplay<'_> {
                         &self.words[word_idx],
                         is_current,
                         self.cursor_position,
+                        self.blind,
+                        self.nospace,
                         correct_style,
                         incorrect_style,
                         extra_style,
@@ -234,6 +317,8 @@ fn render_word(
     word_state: &WordState,
     is_current: bool,
     cursor_pos: usize,
+    blind: bool,
+    nospace: bool,
     correct_style: Style,
     incorrect_style: Style,
     extra_style: Style,
@@ -241,21 +326,23 @@ fn render_word(
     cursor_style: Style,
     extra_cursor_style: Style,
 ) -> u16 {
-    let word_chars: Vec<char> = word_state.word.chars().collect();
-    let typed_chars: Vec<char> = word_state.typed.chars().collect();
+    let word_graphemes = graphemes(&word_state.word);
+    let typed_graphemes = graphemes(&word_state.typed);
+
+    // Skipped indentation is just room
+    x = x.saturating_add(display_width(&word_state.indent) as u16);
 
     // Render target characters
-    for i in 0..word_chars.len() {
+    for (i, g) in word_graphemes.iter().enumerate() {
         if x >= max_x {
             return x;
         }
         let is_cursor = is_current && i == cursor_pos;
-        let ch = word_chars[i];
 
         let style = if is_cursor {
             cursor_style
-        } else if i < typed_chars.len() {
-            if typed_chars[i] == ch {
+        } else if let Some(cr) = word_state.chars.get(i) {
+            if cr.correct || blind {
                 correct_style
             } else {
                 incorrect_style
@@ -264,12 +351,20 @@ fn render_word(
             dim_style
         };
 
-        buf.set_string(x, y, ch.to_string(), style);
-        x += 1;
+        // A mistyped space would be invisible
+        let shown = if *g == " " && style == incorrect_style { "·" } else { *g };
+        buf.set_string(x, y, shown, style);
+        x += grapheme_width(g) as u16;
     }
 
     // Render extra characters (typed beyond word length)
-    for i in word_chars.len()..typed_chars.len() {
+    let shown_extras = if blind { 0 } else { typed_graphemes.len() };
+    for (i, g) in typed_graphemes
+        .iter()
+        .enumerate()
+        .take(shown_extras)
+        .skip(word_graphemes.len())
+    {
         if x >= max_x {
             return x;
         }
@@ -279,20 +374,21 @@ fn render_word(
         } else {
             extra_style
         };
-        buf.set_string(x, y, typed_chars[i].to_string(), style);
-        x += 1;
+        buf.set_string(x, y, *g, style);
+        x += grapheme_width(g) as u16;
     }
 
-    // Trailing space / cursor-at-end
-    if x < max_x {
-        let cursor_at_end =
-            is_current && cursor_pos >= word_chars.len().max(typed_chars.len());
-        let style = if cursor_at_end {
-            cursor_style
-        } else {
-            Style::default()
+    // Trailing space / cursor-at-end (nospace only needs the cursor cell).
+    // The end of a line of code shows the Enter it takes.
+    let cursor_at_end = is_current && cursor_pos >= word_graphemes.len().max(shown_extras);
+    if x < max_x && (!nospace || cursor_at_end || word_state.line_breaks > 0) {
+        let (separator, style) = match (word_state.line_breaks > 0, cursor_at_end) {
+            (true, true) => ("↵", cursor_style),
+            (true, false) => ("↵", dim_style),
+            (false, true) => (" ", cursor_style),
+            (false, false) => (" ", Style::default()),
         };
-        buf.set_string(x, y, " ", style);
+        buf.set_string(x, y, separator, style);
         x += 1;
     }
 
@@ -0,0 +1,83 @@
+use crate::data::themes::parse_hex;
+use crate::engine::graphemes::display_width;
+use crate::types::{ThemeColors, WordTiming};
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Widget;
+
+pub struct WordHistory<'a> {
+    pub words: &'a [WordTiming],
+    pub theme: &'a ThemeColors,
+    pub terminal_width: u16,
+}
+
+impl Widget for WordHistory<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.height < 3 {
+            return;
+        }
+
+        let correct = parse_hex(&self.theme.correct);
+        let incorrect = parse_hex(&self.theme.incorrect);
+        let dim = parse_hex(&self.theme.text_dim);
+        let accent = parse_hex(&self.theme.accent);
+        let dim_style = Style::default().fg(dim);
+
+        // Same footprint as the wpm chart it replaces
+        let width = (self.terminal_width.saturating_sub(10) as usize).min(70);
+        let x_start = area.x + area.width.saturating_sub(width as u16) / 2;
+        let mut y = area.y;
+
+        let title = Line::from(Span::styled("word history (burst wpm)", dim_style));
+        buf.set_line(x_start, y, &title, area.width);
+        y += 1;
+
+        if self.words.is_empty() {
+            let line = Line::from(Span::styled("no completed words", dim_style));
+            buf.set_line(x_start, y, &line, area.width);
+            return;
+        }
+
+        // Words flow left to right; the last row is kept for the summary
+        let last_row = area.y + area.height - 1;
+        let mut x = 0usize;
+        let mut truncated = false;
+        for timing in self.words {
+            let burst = format!("{}", timing.burst_wpm.round() as u32);
+            let token_width = display_width(&timing.typed) + 1 + burst.len();
+            if x > 0 && x + token_width > width {
+                y += 1;
+                x = 0;
+            }
+            if y >= last_row {
+                truncated = true;
+                break;
+            }
+            let word_color = if timing.correct { correct } else { incorrect };
+            let line = Line::from(vec![
+                Span::styled(timing.typed.clone(), Style::default().fg(word_color)),
+                Span::styled(format!(" {burst}"), dim_style),
+            ]);
+            buf.set_line(x_start + x as u16, y, &line, (width - x) as u16);
+            x += token_width + 3;
+        }
+
+        // Summary: the slowest words are the ones worth drilling
+        let mut slowest: Vec<&WordTiming> = self.words.iter().collect();
+        slowest.sort_by(|a, b| a.burst_wpm.total_cmp(&b.burst_wpm));
+        let mut spans = vec![Span::styled(
+            if truncated { "…  slowest: " } else { "slowest: " },
+            dim_style,
+        )];
+        for timing in slowest.iter().take(3) {
+            spans.push(Span::styled(timing.word.clone(), Style::default().fg(accent)));
+            spans.push(Span::styled(
+                format!(" {}  ", timing.burst_wpm.round() as u32),
+                dim_style,
+            ));
+        }
+        buf.set_line(x_start, last_row, &Line::from(spans), area.width);
+    }
+}
@@ -0,0 +1,92 @@
+use buddytype::engine::adaptive::*;
+use buddytype::types::{KeyStats, KeyTarget};
+use std::collections::BTreeMap;
+
+fn key(correct: usize, incorrect: usize, avg_press_ms: f64) -> KeyStats {
+    KeyStats {
+        correct,
+        incorrect,
+        avg_press_ms,
+        timed_presses: correct,
+        ..KeyStats::default()
+    }
+}
+
+fn stats(keys: &[(&str, KeyStats)]) -> BTreeMap<String, KeyStats> {
+    keys.iter().map(|(k, s)| (k.to_string(), s.clone())).collect()
+}
+
+fn words(list: &[&str]) -> Vec<String> {
+    list.iter().map(|w| w.to_string()).collect()
+}
+
+// ── Merging ─────────────────────────────────────────────────────
+
+#[test]
+fn merging_adds_presses_and_weights_press_times() {
+    let a = stats(&[("a", key(10, 2, 100.0))]);
+    let b = stats(&[("a", key(30, 0, 200.0)), ("b", key(5, 5, 150.0))]);
+    let merged = merge_key_stats([&a, &b]);
+    assert_eq!((merged["a"].correct, merged["a"].incorrect), (40, 2));
+    assert_eq!(merged["a"].timed_presses, 40);
+    assert!((merged["a"].avg_press_ms - 175.0).abs() < 0.01);
+    assert_eq!(merged["b"].presses(), 10);
+}
+
+// ── Weak keys ───────────────────────────────────────────────────
+
+#[test]
+fn error_prone_and_slow_keys_are_weakest() {
+    let keys = stats(&[
+        ("a", key(100, 0, 150.0)),
+        ("e", key(100, 0, 150.0)),
+        ("q", key(80, 20, 150.0)),
+        ("z", key(100, 0, 400.0)),
+    ]);
+    let targets = weakest_keys(&keys, &BTreeMap::new(), &words(&["quiz", "ate"]), 3);
+    let names: Vec<&str> = targets.iter().map(|t| t.key.as_str()).collect();
+    assert_eq!(names, vec!["q", "z"]);
+    assert!((targets[0].error_rate - 0.2).abs() < 0.001);
+}
+
+#[test]
+fn rarely_typed_keys_are_not_judged() {
+    let keys = stats(&[("a", key(100, 0, 100.0)), ("x", key(2, 3, 500.0))]);
+    let targets = weakest_keys(&keys, &BTreeMap::new(), &words(&["ax"]), 3);
+    assert!(targets.is_empty());
+}
+
+#[test]
+fn keys_no_word_contains_are_skipped() {
+    let keys = stats(&[("a", key(100, 0, 100.0)), ("ß", key(50, 50, 100.0))]);
+    let targets = weakest_keys(&keys, &BTreeMap::new(), &words(&["strasse"]), 3);
+    assert!(targets.iter().all(|t| t.key != "ß"));
+}
+
+#[test]
+fn bigrams_overlapping_a_chosen_key_are_passed_over() {
+    let keys = stats(&[("a", key(100, 0, 100.0)), ("h", key(70, 30, 100.0))]);
+    let bigrams = stats(&[
+        ("th", key(60, 40, 100.0)),
+        ("er", key(90, 10, 100.0)),
+        ("at", key(100, 0, 100.0)),
+    ]);
+    let targets = weakest_keys(&keys, &bigrams, &words(&["the", "her", "at"]), 3);
+    let names: Vec<&str> = targets.iter().map(|t| t.key.as_str()).collect();
+    assert_eq!(names, vec!["th", "er"]);
+}
+
+// ── Improvement ─────────────────────────────────────────────────
+
+#[test]
+fn fewer_errors_or_faster_presses_count_as_improved() {
+    let target = KeyTarget {
+        key: "q".into(),
+        error_rate: 0.2,
+        avg_press_ms: 300.0,
+    };
+    assert_eq!(improved(&target, Some(&key(19, 1, 400.0))), Some(true));
+    assert_eq!(improved(&target, Some(&key(8, 2, 250.0))), Some(true));
+    assert_eq!(improved(&target, Some(&key(7, 3, 200.0))), Some(false));
+    assert_eq!(improved(&target, None), None);
+}
@@ -0,0 +1,79 @@
+use buddytype::data::languages::get_available_languages;
+use buddytype::data::snippets::*;
+use buddytype::engine::code_snippet::snippet_words;
+
+#[test]
+fn every_code_language_has_snippets() {
+    for language in get_available_languages() {
+        if language.starts_with("code_") {
+            let collection = get_snippets(language).expect(language);
+            assert_eq!(collection.language, language);
+            assert!(!collection.snippets.is_empty());
+        }
+    }
+}
+
+#[test]
+fn snippet_ids_are_unique_per_language() {
+    for language in get_available_languages() {
+        let Some(collection) = get_snippets(language) else {
+            continue;
+        };
+        let mut ids: Vec<u32> = collection.snippets.iter().map(|s| s.id).collect();
+        ids.sort_unstable();
+        ids.dedup();
+        assert_eq!(ids.len(), collection.snippets.len(), "{language}");
+    }
+}
+
+#[test]
+fn same_seed_picks_same_snippet() {
+    assert_eq!(pick_snippet("code_python", 9).id, pick_snippet("code_python", 9).id);
+}
+
+#[test]
+fn natural_languages_fall_back_to_rust() {
+    assert_eq!(snippet_language("english"), "code_rust");
+    assert_eq!(snippet_language("code_go"), "code_go");
+    let snippet = pick_snippet("english", 1);
+    let rust = get_snippets("code_rust").unwrap();
+    assert!(rust.snippets.iter().any(|s| s.id == snippet.id && s.code == snippet.code));
+}
+
+#[test]
+fn lines_end_in_a_line_break() {
+    let words = snippet_words("fn main() {\n    run();\n}");
+    assert_eq!(words, vec!["fn", "main()", "{\n", "    run();\n", "}"]);
+}
+
+#[test]
+fn indentation_goes_on_the_first_word() {
+    let words = snippet_words("if x:\n    a = 1");
+    assert_eq!(words, vec!["if", "x:\n", "    a", "=", "1"]);
+}
+
+#[test]
+fn tabs_expand_to_spaces() {
+    let words = snippet_words("{\n\tx\n\t\ty\n}");
+    assert_eq!(words, vec!["{\n", "    x\n", "        y\n", "}"]);
+}
+
+#[test]
+fn blank_lines_fold_into_the_line_break() {
+    let words = snippet_words("a\n\n\nb\n");
+    assert_eq!(words, vec!["a\n\n\n", "b"]);
+}
+
+#[test]
+fn every_snippet_splits_into_words() {
+    for language in get_available_languages() {
+        let Some(collection) = get_snippets(language) else {
+            continue;
+        };
+        for snippet in &collection.snippets {
+            let words = snippet_words(&snippet.code);
+            assert!(!words.is_empty());
+            assert!(!words.last().unwrap().ends_with('\n'), "{language} #{}", snippet.id);
+        }
+    }
+}
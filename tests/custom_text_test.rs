@@ -0,0 +1,50 @@
+use buddytype::engine::custom_text::*;
+
+#[test]
+fn tokenizes_on_any_whitespace() {
+    let words = tokenize("Hello,  World!\n\tNew line.");
+    assert_eq!(words, vec!["Hello,", "World!", "New", "line."]);
+}
+
+#[test]
+fn tokenize_keeps_punctuation_and_case() {
+    let words = tokenize("Don't PANIC: see §4 (\"Errors\").");
+    assert_eq!(words, vec!["Don't", "PANIC:", "see", "§4", "(\"Errors\")."]);
+}
+
+#[test]
+fn empty_text_has_no_words() {
+    assert!(tokenize("  \n\t ").is_empty());
+    assert!(build_custom_words("", true, 3, 1).is_empty());
+}
+
+#[test]
+fn repeats_text_in_order() {
+    let words = build_custom_words("a b c", false, 2, 0);
+    assert_eq!(words, vec!["a", "b", "c", "a", "b", "c"]);
+}
+
+#[test]
+fn repeat_of_zero_still_types_text_once() {
+    assert_eq!(build_custom_words("a b", false, 0, 0), vec!["a", "b"]);
+}
+
+#[test]
+fn shuffle_keeps_every_word() {
+    let text = "one two three four five six seven eight nine ten";
+    let mut shuffled = build_custom_words(text, true, 1, 42);
+    shuffled.sort();
+    let mut original = tokenize(text);
+    original.sort();
+    assert_eq!(shuffled, original);
+}
+
+#[test]
+fn shuffle_is_reproducible_from_seed() {
+    let text = "one two three four five six seven eight nine ten";
+    assert_eq!(
+        build_custom_words(text, true, 2, 7),
+        build_custom_words(text, true, 2, 7)
+    );
+    assert_ne!(build_custom_words(text, true, 1, 7), tokenize(text));
+}
@@ -0,0 +1,79 @@
+use buddytype::engine::fail_conditions::*;
+use buddytype::engine::input_processor::*;
+use buddytype::engine::stats_calculator::calculate_stats;
+use buddytype::types::{FailReason, TestConfig};
+
+fn typed(text: &str, target: &[&str]) -> buddytype::types::InputState {
+    let words: Vec<String> = target.iter().map(|w| w.to_string()).collect();
+    let mut state = create_input_state(&words);
+    for ch in text.chars() {
+        state = if ch == ' ' {
+            process_space(state)
+        } else {
+            process_char(state, ch)
+        };
+    }
+    state
+}
+
+#[test]
+fn no_conditions_never_fail() {
+    let config = TestConfig::default();
+    let state = typed("xxxx", &["hello"]);
+    let stats = calculate_stats(&state, 30.0);
+    assert_eq!(check_failure(&config, &state, &stats, 30.0), None);
+}
+
+#[test]
+fn sudden_death_fails_on_first_error() {
+    let config = TestConfig {
+        sudden_death: true,
+        ..Default::default()
+    };
+    let state = typed("he", &["hello"]);
+    let stats = calculate_stats(&state, 0.5);
+    assert_eq!(check_failure(&config, &state, &stats, 0.5), None);
+
+    let state = typed("hx", &["hello"]);
+    let stats = calculate_stats(&state, 0.5);
+    assert_eq!(
+        check_failure(&config, &state, &stats, 0.5),
+        Some(FailReason::SuddenDeath)
+    );
+}
+
+#[test]
+fn min_accuracy_waits_for_grace_period() {
+    let config = TestConfig {
+        min_accuracy: Some(90),
+        ..Default::default()
+    };
+    let state = typed("hxllo", &["hello"]);
+    let early = calculate_stats(&state, 1.0);
+    assert_eq!(check_failure(&config, &state, &early, 1.0), None);
+
+    let later = calculate_stats(&state, FAIL_GRACE_SECONDS);
+    assert_eq!(
+        check_failure(&config, &state, &later, FAIL_GRACE_SECONDS),
+        Some(FailReason::MinAccuracy)
+    );
+}
+
+#[test]
+fn min_wpm_fails_when_live_wpm_is_too_low() {
+    let config = TestConfig {
+        min_wpm: Some(40),
+        ..Default::default()
+    };
+    // One 5-letter word + space in 10s = 7.2 wpm
+    let state = typed("hello ", &["hello", "world"]);
+    let stats = calculate_stats(&state, 10.0);
+    assert_eq!(
+        check_failure(&config, &state, &stats, 10.0),
+        Some(FailReason::MinWpm)
+    );
+
+    // Same in 1s = 72 wpm
+    let stats = calculate_stats(&state, 1.0);
+    assert_eq!(check_failure(&config, &state, &stats, FAIL_GRACE_SECONDS), None);
+}
@@ -0,0 +1,77 @@
+use buddytype::engine::funbox::apply_funbox;
+use buddytype::types::Funbox;
+
+fn words(list: &[&str]) -> Vec<String> {
+    list.iter().map(|w| w.to_string()).collect()
+}
+
+#[test]
+fn mirror_reverses_each_word() {
+    let out = apply_funbox(&words(&["hello", "café"]), Funbox::Mirror, 0);
+    assert_eq!(out, words(&["olleh", "éfac"]));
+}
+
+#[test]
+fn random_case_keeps_letters() {
+    let input = words(&["random", "case", "letters", "everywhere"]);
+    let out = apply_funbox(&input, Funbox::RandomCase, 1);
+    for (a, b) in input.iter().zip(&out) {
+        assert_eq!(a.to_lowercase(), b.to_lowercase());
+    }
+    assert!(out.iter().any(|w| w.chars().any(|c| c.is_uppercase())));
+}
+
+#[test]
+fn capitals_capitalizes_every_word() {
+    let out = apply_funbox(&words(&["the", "über", "1st"]), Funbox::Capitals, 0);
+    assert_eq!(out, words(&["The", "Über", "1st"]));
+}
+
+#[test]
+fn gibberish_uses_letters_from_the_words() {
+    let input = words(&["abc", "cab"]);
+    let out = apply_funbox(&input, Funbox::Gibberish, 5);
+    assert_eq!(out.len(), 2);
+    for w in &out {
+        assert!((1..=7).contains(&w.chars().count()));
+        assert!(w.chars().all(|c| "abc".contains(c)));
+    }
+}
+
+#[test]
+fn numbers_are_digits_only() {
+    let out = apply_funbox(&words(&["a", "hello", "extraordinary"]), Funbox::Numbers, 3);
+    assert_eq!(out[0].len(), 1);
+    assert_eq!(out[1].len(), 5);
+    assert_eq!(out[2].len(), 7);
+    assert!(out.iter().all(|w| w.chars().all(|c| c.is_ascii_digit())));
+}
+
+#[test]
+fn nospace_leaves_words_alone() {
+    let input = words(&["run", "together"]);
+    assert_eq!(apply_funbox(&input, Funbox::Nospace, 0), input);
+}
+
+#[test]
+fn layout_mirror_swaps_keys_across_each_row() {
+    let out = apply_funbox(&words(&["qwerty", "Asdf", "zxcv", "g-1"]), Funbox::LayoutMirror, 0);
+    assert_eq!(out, words(&["poiuyt", "Lkjh", "mnbv", "g-1"]));
+}
+
+#[test]
+fn random_funboxes_are_reproducible_from_seed() {
+    let input = words(&["some", "words", "to", "shuffle", "around"]);
+    for funbox in [Funbox::RandomCase, Funbox::Gibberish, Funbox::Numbers] {
+        assert_eq!(apply_funbox(&input, funbox, 9), apply_funbox(&input, funbox, 9));
+    }
+}
+
+#[test]
+fn numbers_funbox_serializes_as_58008() {
+    assert_eq!(serde_json::to_string(&Funbox::Numbers).unwrap(), "\"58008\"");
+    assert_eq!(
+        serde_json::from_str::<Funbox>("\"layout_mirror\"").unwrap(),
+        Funbox::LayoutMirror
+    );
+}
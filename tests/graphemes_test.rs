@@ -0,0 +1,24 @@
+use buddytype::engine::graphemes::*;
+
+#[test]
+fn counts_graphemes_not_bytes() {
+    assert_eq!(grapheme_count("café"), 4);
+    assert_eq!(grapheme_count("cafe\u{301}"), 4);
+    assert_eq!(grapheme_count("straße"), 6);
+}
+
+#[test]
+fn pops_whole_grapheme_cluster() {
+    let mut s = String::from("cafe\u{301}");
+    assert!(pop_grapheme(&mut s));
+    assert_eq!(s, "caf");
+    let mut empty = String::new();
+    assert!(!pop_grapheme(&mut empty));
+}
+
+#[test]
+fn measures_display_width_in_columns() {
+    assert_eq!(display_width("niño"), 4);
+    assert_eq!(display_width("日本"), 4);
+    assert_eq!(grapheme_width("\u{301}"), 1);
+}
@@ -1,4 +1,5 @@
 use buddytype::engine::input_processor::*;
+use buddytype::types::{Confidence, InputOptions, InputState, KeystrokeKind, StopOnError};
 
 // ── createInputState ────────────────────────────────────────────
 
@@ -267,46 +268,46 @@ fn ctrl_backspace_clears_extra_characters() {
 #[test]
 fn delegates_backspace_correctly() {
     let state = create_input_state(&["hi".into()]);
-    let state = process_keystroke(state, "h", false, false);
-    let state = process_keystroke(state, "", true, false);
+    let state = process_keystroke(state, "h", false, false, 0.0);
+    let state = process_keystroke(state, "", true, false, 0.0);
     assert_eq!(state.words[0].typed, "");
 }
 
 #[test]
 fn delegates_ctrl_backspace_correctly() {
     let state = create_input_state(&["hi".into()]);
-    let state = process_keystroke(state, "h", false, false);
-    let state = process_keystroke(state, "i", false, false);
-    let state = process_keystroke(state, "", true, true);
+    let state = process_keystroke(state, "h", false, false, 0.0);
+    let state = process_keystroke(state, "i", false, false, 0.0);
+    let state = process_keystroke(state, "", true, true, 0.0);
     assert_eq!(state.words[0].typed, "");
 }
 
 #[test]
 fn delegates_space_correctly() {
     let state = create_input_state(&["a".into(), "b".into()]);
-    let state = process_keystroke(state, "a", false, false);
-    let state = process_keystroke(state, " ", false, false);
+    let state = process_keystroke(state, "a", false, false, 0.0);
+    let state = process_keystroke(state, " ", false, false, 0.0);
     assert_eq!(state.current_word_index, 1);
 }
 
 #[test]
 fn ignores_control_characters() {
     let state = create_input_state(&["hi".into()]);
-    let next = process_keystroke(state, "\x01", false, false);
+    let next = process_keystroke(state, "\x01", false, false, 0.0);
     assert_eq!(next.words[0].typed, "");
 }
 
 #[test]
 fn ignores_empty_string_input() {
     let state = create_input_state(&["hi".into()]);
-    let next = process_keystroke(state, "", false, false);
+    let next = process_keystroke(state, "", false, false, 0.0);
     assert_eq!(next.words[0].typed, "");
 }
 
 #[test]
 fn ignores_multi_char_input() {
     let state = create_input_state(&["hi".into()]);
-    let next = process_keystroke(state, "abc", false, false);
+    let next = process_keystroke(state, "abc", false, false, 0.0);
     assert_eq!(next.words[0].typed, "");
 }
 
@@ -362,3 +363,629 @@ fn does_not_decrement_counters_on_backspace() {
     assert_eq!(state.keypress_correct, 1);
     assert_eq!(state.keypress_incorrect, 1);
 }
+
+// ── Keystroke log ───────────────────────────────────────────────
+
+#[test]
+fn records_every_keystroke_with_timestamp_and_position() {
+    let state = create_input_state(&["hi".into(), "ok".into()]);
+    let state = process_keystroke(state, "h", false, false, 0.0);
+    let state = process_keystroke(state, "x", false, false, 120.0);
+    let state = process_keystroke(state, "", true, false, 250.0);
+    let state = process_keystroke(state, "i", false, false, 400.0);
+    let state = process_keystroke(state, " ", false, false, 510.0);
+    let state = process_keystroke(state, "o", false, false, 640.0);
+    let state = process_keystroke(state, "", true, true, 700.0);
+
+    let log = &state.keystrokes;
+    assert_eq!(log.len(), 7);
+    assert_eq!(log[0].kind, KeystrokeKind::Char('h'));
+    assert_eq!(log[0].correct, Some(true));
+    assert_eq!(log[1].kind, KeystrokeKind::Char('x'));
+    assert_eq!((log[1].word_index, log[1].char_index), (0, 1));
+    assert_eq!(log[1].correct, Some(false));
+    assert_eq!(log[2].kind, KeystrokeKind::Backspace);
+    assert_eq!(log[2].correct, None);
+    assert_eq!(log[4].kind, KeystrokeKind::Space);
+    assert_eq!(log[4].correct, Some(true));
+    assert_eq!((log[5].word_index, log[5].char_index), (1, 0));
+    assert_eq!(log[6].kind, KeystrokeKind::CtrlBackspace);
+    assert_eq!(log[6].timestamp_ms, 700.0);
+}
+
+#[test]
+fn does_not_record_ignored_input() {
+    let state = create_input_state(&["hi".into()]);
+    let state = process_keystroke(state, "\x01", false, false, 10.0);
+    let state = process_keystroke(state, "abc", false, false, 20.0);
+    assert!(state.keystrokes.is_empty());
+}
+
+#[test]
+fn records_unscored_space_on_empty_word_without_verdict() {
+    let state = create_input_state(&["hi".into()]);
+    let state = process_keystroke(state, " ", false, false, 10.0);
+    assert_eq!(state.keystrokes.len(), 1);
+    assert_eq!(state.keystrokes[0].correct, None);
+}
+
+// ── Multibyte words ─────────────────────────────────────────────
+
+#[test]
+fn scores_accented_characters_by_grapheme() {
+    let mut state = create_input_state(&["café".into()]);
+    for ch in "café".chars() {
+        state = process_char(state, ch);
+    }
+    assert_eq!(state.cursor_position, 4);
+    assert_eq!(state.words[0].chars.len(), 4);
+    assert!(state.words[0].chars.iter().all(|c| c.correct && !c.extra));
+}
+
+#[test]
+fn detects_extra_characters_after_multibyte_word() {
+    let mut state = create_input_state(&["über".into()]);
+    for ch in "übers".chars() {
+        state = process_char(state, ch);
+    }
+    assert!(state.words[0].chars[4].extra);
+    assert!(!state.words[0].chars[3].extra);
+    assert_eq!(state.cursor_position, 5);
+}
+
+#[test]
+fn wrong_accent_is_incorrect_and_keeps_alignment() {
+    let mut state = create_input_state(&["niño".into()]);
+    for ch in "nino".chars() {
+        state = process_char(state, ch);
+    }
+    let correct: Vec<bool> = state.words[0].chars.iter().map(|c| c.correct).collect();
+    assert_eq!(correct, vec![true, true, false, true]);
+}
+
+#[test]
+fn combining_mark_merges_into_previous_grapheme() {
+    // "é" typed as 'e' + U+0301 against a decomposed target
+    let mut state = create_input_state(&["cafe\u{301}".into()]);
+    for ch in "cafe".chars() {
+        state = process_char(state, ch);
+    }
+    assert!(!state.words[0].chars[3].correct);
+    state = process_char(state, '\u{301}');
+    assert_eq!(state.words[0].chars.len(), 4);
+    assert!(state.words[0].chars[3].correct);
+    assert_eq!(state.cursor_position, 4);
+}
+
+#[test]
+fn backspace_removes_whole_grapheme() {
+    let mut state = create_input_state(&["straße".into()]);
+    for ch in "straß".chars() {
+        state = process_char(state, ch);
+    }
+    state = process_backspace(state);
+    assert_eq!(state.words[0].typed, "stra");
+    assert_eq!(state.words[0].chars.len(), 4);
+    assert_eq!(state.cursor_position, 4);
+}
+
+#[test]
+fn returning_to_multibyte_word_restores_grapheme_cursor() {
+    let mut state = create_input_state(&["été".into(), "oui".into()]);
+    for ch in "été".chars() {
+        state = process_char(state, ch);
+    }
+    state = process_space(state);
+    state = process_backspace(state);
+    assert_eq!(state.current_word_index, 0);
+    assert_eq!(state.cursor_position, 3);
+}
+
+// ── appendWords ─────────────────────────────────────────────────
+
+#[test]
+fn append_words_extends_list_and_keeps_progress() {
+    let mut state = create_input_state(&["a".into()]);
+    state = process_char(state, 'a');
+    state = append_words(state, &["b".into(), "c".into()]);
+    assert_eq!(state.words.len(), 3);
+    assert_eq!(state.words[0].typed, "a");
+    assert_eq!(state.words[2].word, "c");
+
+    // The test no longer finishes after the first word
+    state = process_space(state);
+    assert!(!state.finished);
+    assert_eq!(state.current_word_index, 1);
+}
+
+// ── Stop on error ───────────────────────────────────────────────
+
+fn stop_on(mode: StopOnError, words: &[&str]) -> buddytype::types::InputState {
+    let words: Vec<String> = words.iter().map(|w| w.to_string()).collect();
+    create_input_state_with_options(
+        &words,
+        InputOptions {
+            stop_on_error: mode,
+            ..Default::default()
+        },
+    )
+}
+
+#[test]
+fn letter_mode_counts_but_does_not_insert_wrong_char() {
+    let state = stop_on(StopOnError::Letter, &["hi"]);
+    let state = process_char(state, 'x');
+    assert_eq!(state.words[0].typed, "");
+    assert!(state.words[0].chars.is_empty());
+    assert_eq!(state.cursor_position, 0);
+    assert_eq!(state.keypress_incorrect, 1);
+    assert_eq!(state.rejected_chars, 1);
+    assert_eq!(state.historical_error_chars, 1);
+
+    let state = process_char(state, 'h');
+    assert_eq!(state.words[0].typed, "h");
+    assert_eq!(state.keypress_correct, 1);
+}
+
+#[test]
+fn letter_mode_rejects_extra_chars() {
+    let state = stop_on(StopOnError::Letter, &["hi"]);
+    let state = process_char(state, 'h');
+    let state = process_char(state, 'i');
+    let state = process_char(state, 'x');
+    assert_eq!(state.words[0].typed, "hi");
+    assert_eq!(state.rejected_chars, 1);
+}
+
+#[test]
+fn letter_mode_blocks_space_on_incomplete_word() {
+    let state = stop_on(StopOnError::Letter, &["hi", "ok"]);
+    let state = process_char(state, 'h');
+    let state = process_space(state);
+    assert_eq!(state.current_word_index, 0);
+    assert!(!state.words[0].completed);
+    assert_eq!(state.keypress_incorrect, 1);
+}
+
+#[test]
+fn word_mode_inserts_errors_but_blocks_space_until_correct() {
+    let state = stop_on(StopOnError::Word, &["hi", "ok"]);
+    let state = process_char(state, 'h');
+    let state = process_char(state, 'x');
+    assert_eq!(state.words[0].typed, "hx");
+
+    let state = process_space(state);
+    assert_eq!(state.current_word_index, 0);
+    assert_eq!(state.keypress_incorrect, 2);
+
+    let state = process_backspace(state);
+    let state = process_char(state, 'i');
+    let state = process_space(state);
+    assert_eq!(state.current_word_index, 1);
+    assert!(state.words[0].completed);
+}
+
+#[test]
+fn word_mode_finishes_only_on_correct_last_word() {
+    let state = stop_on(StopOnError::Word, &["a"]);
+    let state = process_char(state, 'b');
+    let state = process_space(state);
+    assert!(!state.finished);
+    let state = process_backspace(state);
+    let state = process_char(state, 'a');
+    let state = process_space(state);
+    assert!(state.finished);
+}
+
+// ── Confidence & freedom ────────────────────────────────────────
+
+fn with_options(words: &[&str], options: InputOptions) -> buddytype::types::InputState {
+    let words: Vec<String> = words.iter().map(|w| w.to_string()).collect();
+    create_input_state_with_options(&words, options)
+}
+
+#[test]
+fn locked_correct_words_cannot_be_reopened() {
+    let options = InputOptions {
+        lock_correct_words: true,
+        ..Default::default()
+    };
+    let state = with_options(&["hi", "there"], options);
+    let state = process_char(state, 'h');
+    let state = process_char(state, 'i');
+    let state = process_space(state);
+    let state = process_backspace(state);
+    assert_eq!(state.current_word_index, 1);
+    assert!(state.words[0].completed);
+}
+
+#[test]
+fn locked_mistyped_words_can_still_be_reopened() {
+    let options = InputOptions {
+        lock_correct_words: true,
+        ..Default::default()
+    };
+    let state = with_options(&["hi", "there"], options);
+    let state = process_char(state, 'h');
+    let state = process_char(state, 'x');
+    let state = process_space(state);
+    let state = process_backspace(state);
+    assert_eq!(state.current_word_index, 0);
+    assert_eq!(state.cursor_position, 2);
+}
+
+#[test]
+fn confidence_on_corrects_within_word_only() {
+    let options = InputOptions {
+        confidence: Confidence::On,
+        ..Default::default()
+    };
+    let state = with_options(&["hi", "there"], options);
+    let state = process_char(state, 'x');
+    let state = process_backspace(state);
+    assert_eq!(state.words[0].typed, "");
+
+    let state = process_char(state, 'x');
+    let state = process_space(state);
+    let state = process_backspace(state);
+    assert_eq!(state.current_word_index, 1);
+    assert!(state.words[0].completed);
+}
+
+#[test]
+fn confidence_max_blocks_all_backspace() {
+    let options = InputOptions {
+        confidence: Confidence::Max,
+        ..Default::default()
+    };
+    let state = with_options(&["hi"], options);
+    let state = process_char(state, 'h');
+    let state = process_char(state, 'x');
+    let state = process_backspace(state);
+    assert_eq!(state.words[0].typed, "hx");
+    let state = process_ctrl_backspace(state);
+    assert_eq!(state.words[0].typed, "hx");
+    assert_eq!(state.cursor_position, 2);
+}
+
+#[test]
+fn confidence_is_enforced_through_process_keystroke() {
+    let options = InputOptions {
+        confidence: Confidence::Max,
+        ..Default::default()
+    };
+    let state = with_options(&["hi"], options);
+    let state = process_keystroke(state, "h", false, false, 0.0);
+    let state = process_keystroke(state, "", true, false, 10.0);
+    assert_eq!(state.words[0].typed, "h");
+    assert_eq!(state.keystrokes.len(), 2);
+}
+
+// ── Lazy mode ───────────────────────────────────────────────────
+
+fn lazy(words: &[&str], accents: &[(&str, &str)]) -> buddytype::types::InputState {
+    with_options(
+        words,
+        InputOptions {
+            lazy_mode: true,
+            additional_accents: accents
+                .iter()
+                .map(|(a, r)| (a.to_string(), r.to_string()))
+                .collect(),
+            ..Default::default()
+        },
+    )
+}
+
+#[test]
+fn lazy_mode_accepts_unaccented_letters() {
+    let mut state = lazy(&["niño", "más"], &[]);
+    for ch in "nino".chars() {
+        state = process_char(state, ch);
+    }
+    assert!(state.words[0].chars.iter().all(|c| c.correct));
+    assert_eq!(state.words[0].typed, "niño");
+    state = process_space(state);
+    assert_eq!(state.keypress_incorrect, 0);
+}
+
+#[test]
+fn lazy_mode_accepts_multi_letter_spelling() {
+    let mut state = lazy(&["straße"], &[]);
+    for ch in "strasse".chars() {
+        state = process_char(state, ch);
+    }
+    assert_eq!(state.words[0].typed, "straße");
+    assert_eq!(state.words[0].chars.len(), 6);
+    assert!(state.words[0].chars.iter().all(|c| c.correct));
+    assert_eq!(state.cursor_position, 6);
+    assert_eq!(state.keypress_incorrect, 0);
+}
+
+#[test]
+fn lazy_mode_marks_abandoned_spelling_incorrect() {
+    let mut state = lazy(&["für"], &[("ü", "ue")]);
+    for ch in "fur".chars() {
+        state = process_char(state, ch);
+    }
+    let correct: Vec<bool> = state.words[0].chars.iter().map(|c| c.correct).collect();
+    assert_eq!(correct, vec![true, false, true]);
+}
+
+#[test]
+fn lazy_mode_uses_language_additional_accents() {
+    let mut state = lazy(&["für"], &[("ü", "ue")]);
+    for ch in "fuer".chars() {
+        state = process_char(state, ch);
+    }
+    assert_eq!(state.words[0].typed, "für");
+    assert!(state.words[0].chars.iter().all(|c| c.correct));
+}
+
+#[test]
+fn exact_matching_without_lazy_mode() {
+    let mut state = create_input_state(&["été".into()]);
+    for ch in "ete".chars() {
+        state = process_char(state, ch);
+    }
+    assert!(!state.words[0].chars[0].correct);
+    assert_eq!(state.words[0].typed, "ete");
+}
+
+// ── Word timing ─────────────────────────────────────────────────
+
+#[test]
+fn records_word_start_and_completion_times() {
+    let state = create_input_state(&["hi".into(), "ok".into()]);
+    let state = process_keystroke(state, "h", false, false, 100.0);
+    let state = process_keystroke(state, "i", false, false, 250.0);
+    let state = process_keystroke(state, " ", false, false, 400.0);
+    assert_eq!(state.words[0].started_ms, Some(100.0));
+    assert_eq!(state.words[0].completed_ms, Some(400.0));
+    // The next word's clock starts at the space
+    assert_eq!(state.words[1].started_ms, Some(400.0));
+    assert_eq!(state.words[1].completed_ms, None);
+
+    let state = process_keystroke(state, "o", false, false, 500.0);
+    let state = process_keystroke(state, "k", false, false, 600.0);
+    let state = process_keystroke(state, " ", false, false, 700.0);
+    assert!(state.finished);
+    assert_eq!(state.words[1].completed_ms, Some(700.0));
+}
+
+#[test]
+fn blocked_space_does_not_complete_word() {
+    let options = InputOptions {
+        stop_on_error: StopOnError::Word,
+        ..Default::default()
+    };
+    let state = with_options(&["hi", "ok"], options);
+    let state = process_keystroke(state, "x", false, false, 0.0);
+    let state = process_keystroke(state, " ", false, false, 100.0);
+    assert_eq!(state.words[0].completed_ms, None);
+    assert_eq!(state.words[1].started_ms, None);
+}
+
+// ── Zen (open-ended) ────────────────────────────────────────────
+
+fn zen() -> InputState {
+    let options = InputOptions {
+        open_ended: true,
+        ..Default::default()
+    };
+    create_input_state_with_options(&[String::new()], options)
+}
+
+fn type_text(mut state: InputState, text: &str) -> InputState {
+    for ch in text.chars() {
+        state = match ch {
+            ' ' => process_space(state),
+            '\n' => process_enter(state),
+            '\t' => process_tab(state),
+            _ => process_char(state, ch),
+        };
+    }
+    state
+}
+
+#[test]
+fn zen_typed_text_becomes_the_words() {
+    let state = type_text(zen(), "Whatever I want");
+    let words: Vec<&str> = state.words.iter().map(|w| w.word.as_str()).collect();
+    assert_eq!(words, vec!["Whatever", "I", "want"]);
+    assert!(state.words.iter().all(|w| w.typed == w.word));
+    assert_eq!(state.keypress_incorrect, 0);
+}
+
+#[test]
+fn zen_never_finishes_on_space() {
+    let state = type_text(zen(), "a b c ");
+    assert!(!state.finished);
+    assert_eq!(state.current_word_index, 3);
+    assert_eq!(state.words[3].word, "");
+}
+
+#[test]
+fn zen_backspace_removes_from_the_text() {
+    let state = type_text(zen(), "helx");
+    let state = process_backspace(state);
+    let state = type_text(state, "lo");
+    assert_eq!(state.words[0].word, "hello");
+    assert!(state.words[0].chars.iter().all(|c| c.correct));
+
+    let state = process_ctrl_backspace(state);
+    assert_eq!(state.words[0].word, "");
+}
+
+#[test]
+fn finish_input_completes_the_current_word() {
+    let state = type_text(zen(), "one two");
+    let state = finish_input(state, 1500.0);
+    assert!(state.finished);
+    assert!(state.words[1].completed);
+    assert_eq!(state.words[1].completed_ms, Some(1500.0));
+}
+
+#[test]
+fn finish_input_ignores_an_empty_trailing_word() {
+    let state = type_text(zen(), "one ");
+    let state = finish_input(state, 900.0);
+    assert!(state.finished);
+    assert!(!state.words[1].completed);
+}
+
+// ── Nospace ─────────────────────────────────────────────────────
+
+fn nospace(words: &[&str]) -> InputState {
+    let options = InputOptions {
+        nospace: true,
+        ..Default::default()
+    };
+    with_options(words, options)
+}
+
+#[test]
+fn nospace_advances_once_word_is_correct() {
+    let state = type_text(nospace(&["ab", "cd"]), "ab");
+    assert_eq!(state.current_word_index, 1);
+    assert!(state.words[0].completed);
+
+    let state = type_text(state, "cd");
+    assert!(state.finished);
+}
+
+#[test]
+fn nospace_ignores_space() {
+    let state = type_text(nospace(&["ab", "cd"]), "a ");
+    assert_eq!(state.current_word_index, 0);
+    assert_eq!(state.words[0].typed, "a");
+}
+
+#[test]
+fn nospace_waits_for_mistakes_to_be_fixed() {
+    let state = type_text(nospace(&["ab", "cd"]), "xb");
+    assert_eq!(state.current_word_index, 0);
+    let state = process_backspace(state);
+    let state = process_backspace(state);
+    let state = type_text(state, "ab");
+    assert_eq!(state.current_word_index, 1);
+}
+
+#[test]
+fn nospace_space_moves_past_a_wrong_word() {
+    let state = type_text(nospace(&["ab", "cd"]), "xb ");
+    assert_eq!(state.current_word_index, 1);
+    assert!(state.words[0].completed);
+    assert_eq!(state.keypress_incorrect, 2);
+}
+
+#[test]
+fn nospace_does_not_log_ignored_spaces() {
+    let state = nospace(&["hi", "ok"]);
+    let state = process_keystroke(state, "h", false, false, 100.0);
+    let state = process_keystroke(state, " ", false, false, 200.0);
+    assert_eq!(state.current_word_index, 0);
+    assert_eq!(state.keystrokes.len(), 1);
+}
+
+#[test]
+fn nospace_records_word_completion_on_last_letter() {
+    let state = nospace(&["hi", "ok"]);
+    let state = process_keystroke(state, "h", false, false, 100.0);
+    let state = process_keystroke(state, "i", false, false, 200.0);
+    assert_eq!(state.words[0].completed_ms, Some(200.0));
+    assert_eq!(state.words[1].started_ms, Some(200.0));
+}
+
+// ── Code (line breaks and indentation) ──────────────────────────
+
+fn code(words: &[&str], skip_indent: bool) -> InputState {
+    let options = InputOptions {
+        skip_indent,
+        ..Default::default()
+    };
+    with_options(words, options)
+}
+
+#[test]
+fn trailing_newlines_become_line_breaks() {
+    let state = code(&["fn\n", "x", "}\n\n", "y"], true);
+    assert_eq!(state.words[0].word, "fn");
+    assert_eq!(state.words[0].line_breaks, 1);
+    assert_eq!(state.words[1].line_breaks, 0);
+    assert_eq!(state.words[2].line_breaks, 2);
+}
+
+#[test]
+fn enter_moves_on_from_the_end_of_a_line() {
+    let state = type_text(code(&["a\n", "b"], true), "a\n");
+    assert_eq!(state.current_word_index, 1);
+    assert!(state.words[0].completed);
+    assert_eq!(state.keypress_correct, 2);
+}
+
+#[test]
+fn space_at_the_end_of_a_line_is_a_mistake() {
+    let state = type_text(code(&["a\n", "b"], true), "a ");
+    assert_eq!(state.current_word_index, 0);
+    assert_eq!(state.keypress_incorrect, 1);
+}
+
+#[test]
+fn enter_mid_line_is_ignored() {
+    let state = type_text(code(&["a", "b"], true), "a\n");
+    assert_eq!(state.current_word_index, 0);
+    assert_eq!(state.keypress_incorrect, 0);
+}
+
+#[test]
+fn skipped_indentation_is_not_typed() {
+    let state = code(&["if\n", "    x"], true);
+    assert_eq!(state.words[1].word, "x");
+    assert_eq!(state.words[1].indent, "    ");
+
+    let state = type_text(state, "if\nx ");
+    assert!(state.finished);
+    assert_eq!(state.words[1].typed, "x");
+}
+
+#[test]
+fn typed_indentation_takes_spaces() {
+    let state = code(&["if\n", "  x"], false);
+    assert_eq!(state.words[1].word, "  x");
+    let state = type_text(state, "if\n");
+    assert!(expects_indent(&state));
+
+    let state = type_text(state, "  x");
+    assert!(!expects_indent(&state) || state.finished);
+    assert_eq!(state.words[1].typed, "  x");
+    assert!(state.words[1].chars.iter().all(|c| c.correct));
+}
+
+#[test]
+fn tab_types_indentation_to_the_next_tab_stop() {
+    let state = type_text(code(&["if\n", "      x"], false), "if\n\t");
+    assert_eq!(state.words[1].typed, "    ");
+    let state = type_text(state, "\t");
+    assert_eq!(state.words[1].typed, "      ");
+    assert!(!expects_indent(&state));
+
+    // Nothing left to indent: Tab does nothing
+    let state = type_text(state, "\t");
+    assert_eq!(state.words[1].typed, "      ");
+}
+
+#[test]
+fn enter_and_tab_keystrokes_are_logged() {
+    let state = code(&["a\n", "    b"], false);
+    let state = process_keystroke(state, "a", false, false, 100.0);
+    let state = process_keystroke(state, "\n", false, false, 200.0);
+    let state = process_keystroke(state, "\t", false, false, 300.0);
+    let kinds: Vec<KeystrokeKind> = state.keystrokes.iter().map(|k| k.kind).collect();
+    assert_eq!(
+        kinds,
+        vec![KeystrokeKind::Char('a'), KeystrokeKind::Enter, KeystrokeKind::Tab]
+    );
+    assert_eq!(state.words[0].completed_ms, Some(200.0));
+    assert_eq!(state.words[1].typed, "    ");
+}
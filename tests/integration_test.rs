@@ -2,7 +2,8 @@ use buddytype::engine::input_processor::*;
 use buddytype::engine::stats_calculator::*;
 use buddytype::engine::timer::*;
 use buddytype::engine::word_generator::generate_words;
-use buddytype::types::GeneratorConfig;
+use buddytype::data::languages::get_language;
+use buddytype::types::{GeneratorConfig, TestConfig, WordFilter, WordListSize};
 use std::time::{Duration, Instant};
 
 #[test]
@@ -13,6 +14,10 @@ fn simulates_complete_word_mode_test() {
         count: 3,
         punctuation: false,
         numbers: false,
+        seed: None,
+        targets: Vec::new(),
+        list_size: WordListSize::default(),
+        filter: WordFilter::default(),
     });
     assert_eq!(words.len(), 3);
 
@@ -159,3 +164,59 @@ fn going_back_to_previous_word_preserves_state() {
     assert!(!state.words[0].completed);
     assert_eq!(state.cursor_position, 5);
 }
+
+#[test]
+fn turning_freedom_off_locks_correct_words() {
+    let english = get_language("english");
+    assert!(!TestConfig::default().input_options(english).lock_correct_words);
+    let config = TestConfig {
+        freedom: false,
+        ..Default::default()
+    };
+    assert!(config.input_options(english).lock_correct_words);
+}
+
+#[test]
+fn lazy_mode_is_disabled_for_no_lazy_mode_languages() {
+    let config = TestConfig {
+        lazy_mode: true,
+        ..Default::default()
+    };
+    assert!(config.input_options(get_language("french")).lazy_mode);
+    assert!(!config.input_options(get_language("code_rust")).lazy_mode);
+
+    let german = config.input_options(get_language("german"));
+    assert!(german.additional_accents.contains(&("ü".into(), "ue".into())));
+}
+
+#[test]
+fn replaying_a_seed_reproduces_the_test() {
+    let config = GeneratorConfig {
+        language: "english".into(),
+        count: 10,
+        punctuation: true,
+        numbers: false,
+        seed: Some(1234),
+        targets: Vec::new(),
+        list_size: WordListSize::default(),
+        filter: WordFilter::default(),
+    };
+    let run = |words: &[String]| {
+        let mut state = create_input_state(words);
+        // Type every other word wrong so the stats depend on the exact words
+        for (i, w) in words.iter().enumerate() {
+            let typed = if i % 2 == 0 { w.clone() } else { "x".repeat(w.len()) };
+            for ch in typed.chars() {
+                state = process_char(state, ch);
+            }
+            state = process_space(state);
+        }
+        calculate_stats(&state, 10.0)
+    };
+
+    let first = generate_words(&config);
+    let replay = generate_words(&config);
+    assert_eq!(first, replay);
+    assert_eq!(run(&first).wpm, run(&replay).wpm);
+    assert_eq!(run(&first).correct_chars, run(&replay).correct_chars);
+}
@@ -0,0 +1,59 @@
+mod common;
+
+use buddytype::data::languages::*;
+use common::ScratchDir;
+
+// ── Lookup ──────────────────────────────────────────────────────
+
+#[test]
+fn unknown_languages_are_an_error() {
+    assert!(find_language("english").is_ok());
+    let error = find_language("klingon").unwrap_err();
+    assert!(error.contains("klingon"), "{error}");
+}
+
+#[test]
+fn unknown_languages_fall_back_to_english() {
+    assert_eq!(get_language("klingon").name, DEFAULT_LANGUAGE);
+}
+
+// ── Language packs ──────────────────────────────────────────────
+
+#[test]
+fn loads_packs_named_after_their_files() {
+    let dir = ScratchDir::new(
+        "packs",
+        &[
+            ("toki_pona.json", r#"{"name": "toki pona", "words": ["mi", "sina", "ona"]}"#),
+            ("notes.txt", "not a word list"),
+        ],
+    );
+    let (packs, errors) = load_language_packs(&dir.0);
+    assert!(errors.is_empty(), "{errors:?}");
+    assert_eq!(packs.len(), 1);
+    assert_eq!(packs[0].0, "toki_pona");
+    assert_eq!(packs[0].1.words, vec!["mi", "sina", "ona"]);
+}
+
+#[test]
+fn broken_packs_are_reported() {
+    let dir = ScratchDir::new(
+        "broken-packs",
+        &[
+            ("broken.json", "{\"name\": \"broken\", \"words\": ["),
+            ("empty.json", r#"{"name": "empty", "words": []}"#),
+            ("fine.json", r#"{"name": "fine", "words": ["ok"]}"#),
+        ],
+    );
+    let (packs, errors) = load_language_packs(&dir.0);
+    assert_eq!(packs.len(), 1);
+    assert_eq!(errors.len(), 2);
+    assert!(errors[0].starts_with("broken.json: "), "{errors:?}");
+    assert_eq!(errors[1], "empty.json: no words");
+}
+
+#[test]
+fn missing_pack_folder_has_no_packs() {
+    let (packs, errors) = load_language_packs(&std::env::temp_dir().join("buddytype-no-such-dir"));
+    assert!(packs.is_empty() && errors.is_empty());
+}
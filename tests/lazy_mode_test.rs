@@ -0,0 +1,24 @@
+use buddytype::engine::lazy_mode::lazy_equivalent;
+
+#[test]
+fn folds_common_accents() {
+    assert_eq!(lazy_equivalent("é", &[]).as_deref(), Some("e"));
+    assert_eq!(lazy_equivalent("ñ", &[]).as_deref(), Some("n"));
+    assert_eq!(lazy_equivalent("ß", &[]).as_deref(), Some("ss"));
+    assert_eq!(lazy_equivalent("e\u{301}", &[]).as_deref(), Some("e"));
+    assert_eq!(lazy_equivalent("x", &[]), None);
+}
+
+#[test]
+fn preserves_case_of_folded_letter() {
+    assert_eq!(lazy_equivalent("É", &[]).as_deref(), Some("E"));
+    assert_eq!(lazy_equivalent("Æ", &[]).as_deref(), Some("Ae"));
+}
+
+#[test]
+fn additional_accents_override_builtin_table() {
+    let german = vec![("ä".to_string(), "ae".to_string())];
+    assert_eq!(lazy_equivalent("ä", &german).as_deref(), Some("ae"));
+    assert_eq!(lazy_equivalent("Ä", &german).as_deref(), Some("Ae"));
+    assert_eq!(lazy_equivalent("ö", &german).as_deref(), Some("o"));
+}
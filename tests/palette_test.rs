@@ -0,0 +1,110 @@
+use buddytype::data::palette::*;
+use ratatui::style::Color;
+
+fn detect(vars: &[(&str, &str)]) -> ColorSupport {
+    detect_color_support(|name| {
+        vars.iter()
+            .find(|(k, _)| *k == name)
+            .map(|(_, v)| v.to_string())
+    })
+}
+
+// ── Detection ───────────────────────────────────────────────────
+
+#[test]
+fn colorterm_means_truecolor() {
+    assert_eq!(
+        detect(&[("TERM", "xterm-256color"), ("COLORTERM", "truecolor")]),
+        ColorSupport::TrueColor
+    );
+    assert_eq!(
+        detect(&[("TERM", "xterm"), ("COLORTERM", "24bit")]),
+        ColorSupport::TrueColor
+    );
+}
+
+#[test]
+fn term_decides_without_colorterm() {
+    assert_eq!(detect(&[("TERM", "xterm-256color")]), ColorSupport::Ansi256);
+    assert_eq!(detect(&[("TERM", "xterm")]), ColorSupport::Ansi16);
+    assert_eq!(detect(&[("TERM", "linux")]), ColorSupport::Ansi16);
+    assert_eq!(detect(&[("TERM", "dumb")]), ColorSupport::Monochrome);
+}
+
+#[test]
+fn tmux_uses_256_colors_unless_colorterm_says_more() {
+    let tmux = [("TERM", "tmux-256color"), ("TMUX", "/tmp/tmux-1000/default,1,0")];
+    assert_eq!(detect(&tmux), ColorSupport::Ansi256);
+    let truecolor = [
+        ("TERM", "tmux-256color"),
+        ("TMUX", "/tmp/tmux-1000/default,1,0"),
+        ("COLORTERM", "truecolor"),
+    ];
+    assert_eq!(detect(&truecolor), ColorSupport::TrueColor);
+    assert_eq!(
+        detect(&[("TERM", "screen"), ("TMUX", "x")]),
+        ColorSupport::Ansi16
+    );
+}
+
+#[test]
+fn no_color_turns_colors_off() {
+    let vars = [
+        ("TERM", "xterm-256color"),
+        ("COLORTERM", "truecolor"),
+        ("NO_COLOR", "1"),
+    ];
+    assert_eq!(detect(&vars), ColorSupport::Monochrome);
+    // An empty NO_COLOR doesn't count
+    assert_eq!(
+        detect(&[("TERM", "xterm-256color"), ("NO_COLOR", "")]),
+        ColorSupport::Ansi256
+    );
+}
+
+#[test]
+fn override_wins() {
+    let vars = [
+        ("TERM", "tmux-256color"),
+        ("TMUX", "x"),
+        ("BUDDYTYPE_COLORS", "truecolor"),
+    ];
+    assert_eq!(detect(&vars), ColorSupport::TrueColor);
+    assert_eq!(
+        detect(&[("NO_COLOR", "1"), ("BUDDYTYPE_COLORS", "16")]),
+        ColorSupport::Ansi16
+    );
+}
+
+// ── Mapping ─────────────────────────────────────────────────────
+
+#[test]
+fn nearest_256_uses_cube_and_gray_ramp() {
+    assert_eq!(nearest_256(255, 0, 0), 196);
+    assert_eq!(nearest_256(0, 0, 0), 16);
+    assert_eq!(nearest_256(255, 255, 255), 231);
+    // Grays between cube levels land on the gray ramp
+    assert_eq!(nearest_256(0x32, 0x34, 0x37), 236);
+    assert_eq!(nearest_256(0x80, 0x80, 0x80), 244);
+}
+
+#[test]
+fn nearest_16_picks_closest_ansi_color() {
+    assert_eq!(nearest_16(0x32, 0x34, 0x37), Color::Black);
+    assert_eq!(nearest_16(0xca, 0x47, 0x54), Color::Red);
+    assert_eq!(nearest_16(0xd1, 0xd0, 0xc5), Color::Gray);
+    assert_eq!(nearest_16(0x64, 0x66, 0x69), Color::DarkGray);
+}
+
+#[test]
+fn adapt_color_follows_support() {
+    let rgb = Color::Rgb(255, 0, 0);
+    assert_eq!(adapt_color(rgb, ColorSupport::TrueColor), rgb);
+    assert_eq!(adapt_color(rgb, ColorSupport::Ansi256), Color::Indexed(196));
+    assert_eq!(adapt_color(rgb, ColorSupport::Ansi16), Color::LightRed);
+    assert_eq!(adapt_color(rgb, ColorSupport::Monochrome), Color::Reset);
+    assert_eq!(
+        adapt_color(Color::Reset, ColorSupport::Ansi16),
+        Color::Reset
+    );
+}
@@ -0,0 +1,84 @@
+use buddytype::data::languages::get_available_languages;
+use buddytype::data::quotes::*;
+use buddytype::types::QuoteLength;
+
+const LENGTHS: [QuoteLength; 4] = [
+    QuoteLength::Short,
+    QuoteLength::Medium,
+    QuoteLength::Long,
+    QuoteLength::Thicc,
+];
+
+#[test]
+fn every_natural_language_has_quotes() {
+    for language in get_available_languages() {
+        if !language.starts_with("code_") {
+            assert!(has_quotes(language), "{language} has no quotes");
+        }
+    }
+}
+
+#[test]
+fn every_quote_falls_in_a_length_group() {
+    for language in get_available_languages() {
+        let Some(collection) = get_quotes(language) else {
+            continue;
+        };
+        assert_eq!(collection.language, language);
+        let grouped: usize = LENGTHS
+            .iter()
+            .map(|&l| quotes_of_length(collection, l).len())
+            .sum();
+        assert_eq!(grouped, collection.quotes.len(), "{language}");
+    }
+}
+
+#[test]
+fn quote_ids_are_unique_per_language() {
+    for language in get_available_languages() {
+        let Some(collection) = get_quotes(language) else {
+            continue;
+        };
+        let mut ids: Vec<u32> = collection.quotes.iter().map(|q| q.id).collect();
+        ids.sort();
+        ids.dedup();
+        assert_eq!(ids.len(), collection.quotes.len(), "{language}");
+    }
+}
+
+#[test]
+fn picks_quote_of_requested_length() {
+    let english = get_quotes("english").unwrap();
+    for length in LENGTHS {
+        let group = quotes_of_length(english, length);
+        for seed in 0..20 {
+            let quote = pick_quote("english", length, seed);
+            assert!(group.iter().any(|q| q.id == quote.id));
+        }
+    }
+}
+
+#[test]
+fn same_seed_picks_same_quote() {
+    for seed in 0..20 {
+        let a = pick_quote("english", QuoteLength::Medium, seed);
+        let b = pick_quote("english", QuoteLength::Medium, seed);
+        assert_eq!(a.id, b.id);
+    }
+}
+
+#[test]
+fn falls_back_to_any_length_when_group_is_empty() {
+    let german = get_quotes("german").unwrap();
+    assert!(quotes_of_length(german, QuoteLength::Thicc).is_empty());
+    let quote = pick_quote("german", QuoteLength::Thicc, 3);
+    assert!(german.quotes.iter().any(|q| q.id == quote.id));
+}
+
+#[test]
+fn code_languages_borrow_english_quotes() {
+    assert!(!has_quotes("code_rust"));
+    let quote = pick_quote("code_rust", QuoteLength::Short, 1);
+    let english = get_quotes("english").unwrap();
+    assert!(english.quotes.iter().any(|q| q.text == quote.text));
+}
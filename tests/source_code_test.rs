@@ -0,0 +1,191 @@
+mod common;
+
+use buddytype::data::repo::{pick_excerpt, source_files};
+use buddytype::engine::source_code::*;
+use buddytype::types::ExcerptKind;
+use common::ScratchDir;
+use std::path::Path;
+
+const RUST: &str = "\
+use std::fmt;
+
+/// Adds two numbers.
+pub fn add(a: i32, b: i32) -> i32 {
+    a + b // the sum
+}
+
+trait Shape {
+    fn area(&self) -> f64;
+}
+
+impl Shape for Square {
+    fn area(&self) -> f64 {
+        self.size * self.size
+    }
+}
+";
+
+const PYTHON: &str = "\
+import os
+
+def greet(name):
+    # say hello
+    return f\"hello, {name}!\"
+
+class Stack:
+    def push(self, item):
+        self.items.append(item)
+
+    def pop(self):
+        return self.items.pop()
+";
+
+// ── Language detection ──────────────────────────────────────────
+
+#[test]
+fn detects_language_from_extension() {
+    assert_eq!(detect_language(Path::new("src/main.rs")), Some("code_rust"));
+    assert_eq!(detect_language(Path::new("app.py")), Some("code_python"));
+    assert_eq!(detect_language(Path::new("cmd/main.go")), Some("code_go"));
+    assert_eq!(detect_language(Path::new("index.jsx")), Some("code_javascript"));
+    assert_eq!(detect_language(Path::new("types.ts")), Some("code_typescript"));
+    assert_eq!(detect_language(Path::new("README.md")), None);
+    assert_eq!(detect_language(Path::new("Makefile")), None);
+}
+
+// ── Functions ───────────────────────────────────────────────────
+
+#[test]
+fn finds_rust_functions_with_bodies() {
+    let lines: Vec<&str> = RUST.lines().collect();
+    let functions = find_functions(&lines, "code_rust");
+    // The trait method has no body
+    assert_eq!(functions, vec![3..6, 12..15]);
+}
+
+#[test]
+fn finds_python_functions_by_indentation() {
+    let lines: Vec<&str> = PYTHON.lines().collect();
+    let functions = find_functions(&lines, "code_python");
+    assert_eq!(functions, vec![2..5, 7..9, 10..12]);
+}
+
+#[test]
+fn finds_go_and_javascript_functions() {
+    let go = "package main\n\nfunc main() {\n\tfmt.Println(1)\n}\n";
+    let lines: Vec<&str> = go.lines().collect();
+    assert_eq!(find_functions(&lines, "code_go"), vec![2..5]);
+
+    let js = "export async function load() {\n  return 1;\n}\nconst f = (x) => {\n  return x;\n};\n";
+    let lines: Vec<&str> = js.lines().collect();
+    assert_eq!(find_functions(&lines, "code_javascript"), vec![0..3, 3..6]);
+}
+
+#[test]
+fn function_excerpts_are_dedented() {
+    for seed in 0..10 {
+        let (code, range) = extract_excerpt(RUST, "code_rust", ExcerptKind::Functions, seed).unwrap();
+        assert!(code.starts_with("pub fn add") || code.starts_with("fn area"), "{code}");
+        assert!(code.ends_with('}'));
+        assert!(range == (3..6) || range == (12..15));
+    }
+}
+
+#[test]
+fn line_excerpts_start_on_a_non_blank_line() {
+    for seed in 0..20 {
+        let (code, range) = extract_excerpt(PYTHON, "code_python", ExcerptKind::Lines, seed).unwrap();
+        assert!(!code.lines().next().unwrap().trim().is_empty());
+        assert!(range.len() <= LINE_RANGE_LINES);
+    }
+}
+
+#[test]
+fn files_without_functions_fall_back_to_lines() {
+    let code = "x = 1\ny = 2\nprint(x + y)\n";
+    let (text, _) = extract_excerpt(code, "code_python", ExcerptKind::Functions, 1).unwrap();
+    assert!(!text.is_empty());
+}
+
+#[test]
+fn blank_files_have_no_excerpt() {
+    assert_eq!(extract_excerpt("\n\n  \n", "code_rust", ExcerptKind::Lines, 1), None);
+}
+
+#[test]
+fn same_seed_picks_same_excerpt() {
+    let a = extract_excerpt(PYTHON, "code_python", ExcerptKind::Functions, 7);
+    assert_eq!(a, extract_excerpt(PYTHON, "code_python", ExcerptKind::Functions, 7));
+}
+
+// ── Comments ────────────────────────────────────────────────────
+
+#[test]
+fn strips_line_and_block_comments() {
+    let code = "/* header */\nlet a = 1; // one\n/*\n * doc\n */\nlet b = 2;";
+    assert_eq!(strip_comments(code, "code_rust"), "let a = 1;\nlet b = 2;");
+}
+
+#[test]
+fn keeps_comment_markers_inside_strings() {
+    let code = "let url = \"http://example.com\"; // site\nlet c = '/';";
+    assert_eq!(
+        strip_comments(code, "code_rust"),
+        "let url = \"http://example.com\";\nlet c = '/';"
+    );
+    let code = "s = '# not a comment'  # a comment";
+    assert_eq!(strip_comments(code, "code_python"), "s = '# not a comment'");
+}
+
+#[test]
+fn rust_lifetimes_are_not_strings() {
+    let code = "fn f<'a>(s: &'a str) -> &'a str { s } // done";
+    assert_eq!(strip_comments(code, "code_rust"), "fn f<'a>(s: &'a str) -> &'a str { s }");
+}
+
+#[test]
+fn blank_lines_survive_comment_stripping() {
+    let code = "a = 1\n\n# note\nb = 2";
+    assert_eq!(strip_comments(code, "code_python"), "a = 1\n\nb = 2");
+}
+
+// ── Repositories ────────────────────────────────────────────────
+
+#[test]
+fn source_files_skip_unknown_and_vendored_files() {
+    let repo = ScratchDir::new(
+        "files",
+        &[
+            ("src/lib.rs", RUST),
+            ("tools/build.py", PYTHON),
+            ("README.md", "# readme"),
+            ("node_modules/dep/index.js", "function f() {}"),
+            ("target/debug/gen.rs", "fn x() {}"),
+        ],
+    );
+    let files = source_files(&repo.0);
+    let names: Vec<String> = files
+        .iter()
+        .map(|f| f.strip_prefix(&repo.0).unwrap().display().to_string())
+        .collect();
+    assert_eq!(names, vec!["src/lib.rs", "tools/build.py"]);
+}
+
+#[test]
+fn picks_an_excerpt_with_its_language_and_location() {
+    let repo = ScratchDir::new("pick", &[("app/main.py", PYTHON)]);
+    let excerpt = pick_excerpt(&repo.0, &source_files(&repo.0), ExcerptKind::Functions, true, 3).unwrap();
+    assert_eq!(excerpt.language, "code_python");
+    assert_eq!(excerpt.path, "app/main.py");
+    assert!(excerpt.lines.0 >= 1 && excerpt.lines.0 <= excerpt.lines.1);
+    assert!(!excerpt.code.contains('#'));
+}
+
+#[test]
+fn repositories_without_source_files_are_an_error() {
+    let repo = ScratchDir::new("empty", &[("notes.txt", "nothing here")]);
+    let files = source_files(&repo.0);
+    assert!(pick_excerpt(&repo.0, &files, ExcerptKind::Lines, false, 1).is_err());
+    let missing = repo.0.join("missing");
+    assert!(pick_excerpt(&missing, &[], ExcerptKind::Lines, false, 1).is_err());
+}
@@ -1,5 +1,6 @@
 use buddytype::engine::input_processor::*;
 use buddytype::engine::stats_calculator::*;
+use buddytype::types::{InputOptions, StopOnError};
 
 // ── calculateStats ──────────────────────────────────────────────
 
@@ -276,3 +277,231 @@ fn moderate_variation_between_50_and_90() {
     assert!(consistency > 50.0);
     assert!(consistency < 90.0);
 }
+
+// ── Multibyte words ─────────────────────────────────────────────
+
+#[test]
+fn counts_missed_multibyte_chars_as_graphemes() {
+    let mut state = create_input_state(&["déjà".into(), "vu".into()]);
+    state = process_char(state, 'd');
+    state = process_space(state);
+    let stats = calculate_stats(&state, 10.0);
+    assert_eq!(stats.missed_chars, 3);
+}
+
+#[test]
+fn wpm_counts_accented_word_by_graphemes() {
+    let mut state = create_input_state(&["über".into(), "alles".into()]);
+    for c in "über".chars() {
+        state = process_char(state, c);
+    }
+    state = process_space(state);
+    // 4 graphemes + 1 space = 5 chars = 1 word in 12s → 5 WPM
+    let stats = calculate_stats(&state, 12.0);
+    assert!((stats.wpm - 5.0).abs() < 0.01);
+    assert_eq!(stats.correct_chars, 4);
+}
+
+// ── Stop on error ───────────────────────────────────────────────
+
+#[test]
+fn rejected_letters_count_as_incorrect_chars() {
+    let words: Vec<String> = vec!["hi".into(), "ok".into()];
+    let mut state = create_input_state_with_options(
+        &words,
+        InputOptions {
+            stop_on_error: StopOnError::Letter,
+            ..Default::default()
+        },
+    );
+    state = process_char(state, 'h');
+    state = process_char(state, 'x'); // rejected
+    state = process_char(state, 'i');
+    state = process_space(state);
+
+    let stats = calculate_stats(&state, 12.0);
+    assert_eq!(stats.correct_chars, 2);
+    assert_eq!(stats.incorrect_chars, 1);
+    assert_eq!(stats.missed_chars, 0);
+    // hi + space are a fully correct word
+    assert!((stats.wpm - 3.0).abs() < 0.01);
+    // the rejected keystroke still counts towards raw
+    assert!((stats.raw_wpm - 4.0).abs() < 0.01);
+    assert_eq!(stats.accuracy, 75.0);
+}
+
+// ── Per-key stats ───────────────────────────────────────────────
+
+#[test]
+fn key_stats_count_hits_and_substitutions() {
+    let mut state = create_input_state(&["hi".into(), "hi".into()]);
+    state = process_keystroke(state, "h", false, false, 0.0);
+    state = process_keystroke(state, "o", false, false, 100.0);
+    state = process_keystroke(state, "", true, false, 200.0);
+    state = process_keystroke(state, "i", false, false, 300.0);
+    state = process_keystroke(state, " ", false, false, 400.0);
+    state = process_keystroke(state, "j", false, false, 500.0);
+
+    let stats = calculate_stats(&state, 1.0);
+    let h = &stats.key_stats["h"];
+    assert_eq!((h.correct, h.incorrect), (1, 1));
+    assert_eq!(h.substitutions["j"], 1);
+
+    let i = &stats.key_stats["i"];
+    assert_eq!((i.correct, i.incorrect), (1, 1));
+    assert_eq!(i.substitutions["o"], 1);
+}
+
+#[test]
+fn live_stats_leave_out_key_breakdowns() {
+    let mut state = create_input_state(&["hi".into(), "hi".into()]);
+    state = process_keystroke(state, "h", false, false, 0.0);
+    state = process_keystroke(state, "o", false, false, 100.0);
+    state = process_keystroke(state, " ", false, false, 200.0);
+    state = process_keystroke(state, "h", false, false, 300.0);
+
+    let live = calculate_live_stats(&state, 1.0);
+    let full = calculate_stats(&state, 1.0);
+    assert!(live.key_stats.is_empty());
+    assert!(live.bigram_stats.is_empty());
+    assert!(!full.key_stats.is_empty());
+    assert_eq!((live.wpm, live.raw_wpm, live.accuracy), (full.wpm, full.raw_wpm, full.accuracy));
+    assert_eq!(
+        (live.correct_chars, live.incorrect_chars, live.missed_chars),
+        (full.correct_chars, full.incorrect_chars, full.missed_chars)
+    );
+}
+
+#[test]
+fn key_stats_average_time_over_correct_presses() {
+    let mut state = create_input_state(&["aa".into(), "b".into()]);
+    state = process_keystroke(state, "a", false, false, 0.0); // first press: untimed
+    state = process_keystroke(state, "a", false, false, 200.0);
+    state = process_keystroke(state, " ", false, false, 300.0);
+    state = process_keystroke(state, "x", false, false, 350.0); // wrong: untimed
+    state = process_keystroke(state, "", true, false, 400.0);
+    state = process_keystroke(state, "b", false, false, 500.0);
+
+    let stats = calculate_key_stats(&state);
+    assert_eq!(stats["a"].timed_presses, 1);
+    assert!((stats["a"].avg_press_ms - 200.0).abs() < 0.01);
+    assert_eq!(stats["b"].timed_presses, 1);
+    assert!((stats["b"].avg_press_ms - 100.0).abs() < 0.01);
+}
+
+#[test]
+fn key_stats_ignore_extra_characters() {
+    let mut state = create_input_state(&["a".into()]);
+    state = process_keystroke(state, "a", false, false, 0.0);
+    state = process_keystroke(state, "z", false, false, 100.0);
+    let stats = calculate_key_stats(&state);
+    assert_eq!(stats.len(), 1);
+    assert_eq!(stats["a"].incorrect, 0);
+}
+
+#[test]
+fn key_stats_are_keyed_by_grapheme() {
+    let mut state = create_input_state(&["né".into()]);
+    state = process_keystroke(state, "n", false, false, 0.0);
+    state = process_keystroke(state, "e", false, false, 150.0);
+    let stats = calculate_key_stats(&state);
+    assert_eq!(stats["é"].substitutions["e"], 1);
+}
+
+#[test]
+fn bigram_stats_pair_each_key_with_the_one_before_it() {
+    let mut state = create_input_state(&["the".into(), "at".into()]);
+    state = process_keystroke(state, "t", false, false, 0.0);
+    state = process_keystroke(state, "h", false, false, 100.0);
+    state = process_keystroke(state, "r", false, false, 200.0);
+    state = process_keystroke(state, "", true, false, 300.0);
+    state = process_keystroke(state, "e", false, false, 400.0);
+    state = process_keystroke(state, " ", false, false, 500.0);
+    state = process_keystroke(state, "a", false, false, 600.0);
+    state = process_keystroke(state, "t", false, false, 800.0);
+
+    let stats = calculate_bigram_stats(&state);
+    // First letters of words start no bigram
+    assert_eq!(stats.keys().collect::<Vec<_>>(), vec!["at", "he", "th"]);
+    assert_eq!((stats["he"].correct, stats["he"].incorrect), (1, 1));
+    assert_eq!(stats["he"].substitutions["r"], 1);
+    assert!((stats["at"].avg_press_ms - 200.0).abs() < 0.01);
+}
+
+// ── Word history ────────────────────────────────────────────────
+
+#[test]
+fn word_history_reports_burst_per_completed_word() {
+    let mut state = create_input_state(&["hello".into(), "world".into(), "again".into()]);
+    state = process_keystroke(state, "h", false, false, 0.0);
+    for c in "ello".chars() {
+        state = process_keystroke(state, &c.to_string(), false, false, 500.0);
+    }
+    state = process_keystroke(state, " ", false, false, 1000.0);
+    for c in "wrld".chars() {
+        state = process_keystroke(state, &c.to_string(), false, false, 2000.0);
+    }
+    state = process_keystroke(state, " ", false, false, 3000.0);
+
+    let history = calculate_word_history(&state);
+    assert_eq!(history.len(), 2);
+
+    // 5 chars in 1s → 60 wpm
+    assert_eq!(history[0].word, "hello");
+    assert!(history[0].correct);
+    assert_eq!(history[0].duration_ms, 1000.0);
+    assert!((history[0].burst_wpm - 60.0).abs() < 0.01);
+
+    // 4 chars in 2s → 24 wpm
+    assert_eq!(history[1].typed, "wrld");
+    assert!(!history[1].correct);
+    assert!((history[1].burst_wpm - 24.0).abs() < 0.01);
+}
+
+#[test]
+fn word_history_skips_words_without_timing() {
+    let mut state = create_input_state(&["a".into(), "b".into()]);
+    state = process_char(state, 'a');
+    state = process_space(state);
+    assert!(calculate_word_history(&state).is_empty());
+}
+
+// ── Zen ─────────────────────────────────────────────────────────
+
+#[test]
+fn zen_text_counts_fully_towards_wpm() {
+    let options = InputOptions {
+        open_ended: true,
+        ..Default::default()
+    };
+    let mut state = create_input_state_with_options(&[String::new()], options);
+    for ch in "free typing".chars() {
+        state = if ch == ' ' {
+            process_space(state)
+        } else {
+            process_char(state, ch)
+        };
+    }
+    let state = finish_input(state, 6000.0);
+    let stats = calculate_stats(&state, 6.0);
+    assert_eq!(stats.wpm, stats.raw_wpm);
+    assert_eq!(stats.accuracy, 100.0);
+    assert_eq!(stats.correct_chars, 10);
+}
+
+#[test]
+fn nospace_words_do_not_count_spaces() {
+    let options = InputOptions {
+        nospace: true,
+        ..Default::default()
+    };
+    let mut state = create_input_state_with_options(&["hello".into(), "world".into()], options);
+    for ch in "helloworld".chars() {
+        state = process_char(state, ch);
+    }
+    assert!(state.finished);
+    // 10 chars in 6s = 20 wpm, no phantom spaces
+    let stats = calculate_stats(&state, 6.0);
+    assert_eq!(stats.wpm, 20.0);
+    assert_eq!(stats.raw_wpm, 20.0);
+}
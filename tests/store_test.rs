@@ -0,0 +1,95 @@
+mod common;
+
+use buddytype::config::store::{read_keystrokes, read_scores, settings_from_json, store_score};
+use buddytype::types::{Confidence, KeystrokeEvent, KeystrokeKind, ScoreEntry, TestConfig};
+use common::ScratchDir;
+
+fn entry(date: &str) -> ScoreEntry {
+    let json = format!(
+        r#"{{"wpm": 80, "raw_wpm": 85, "accuracy": 97.0, "consistency": 80.0,
+            "language": "english", "mode": "time", "duration": 30, "date": "{date}"}}"#
+    );
+    serde_json::from_str(&json).unwrap()
+}
+
+fn keystrokes(n: usize) -> Vec<KeystrokeEvent> {
+    (0..n)
+        .map(|i| KeystrokeEvent {
+            timestamp_ms: i as f64 * 120.0,
+            kind: KeystrokeKind::Char('a'),
+            word_index: 0,
+            char_index: i,
+            correct: Some(true),
+        })
+        .collect()
+}
+
+#[test]
+fn keystroke_logs_are_kept_out_of_the_scores_file() {
+    let dir = ScratchDir::new("store-logs", &[]);
+    store_score(&dir.0, entry("2026-10-17T10:00:00.123+02:00"), &keystrokes(5));
+
+    let json = std::fs::read_to_string(dir.0.join("scores.json")).unwrap();
+    assert!(!json.contains("timestamp_ms"));
+    let scores = read_scores(&dir.0);
+    assert_eq!(scores.len(), 1);
+    assert!(scores[0].keystrokes.is_empty());
+    assert_eq!(read_keystrokes(&dir.0, &scores[0]), keystrokes(5));
+}
+
+#[test]
+fn logs_of_dropped_scores_are_removed() {
+    let dir = ScratchDir::new("store-prune", &[]);
+    for i in 0..102 {
+        store_score(&dir.0, entry(&format!("2026-10-17T10:00:{i:03}+02:00")), &keystrokes(1));
+    }
+    assert_eq!(read_scores(&dir.0).len(), 100);
+    assert_eq!(std::fs::read_dir(dir.0.join("keystrokes")).unwrap().count(), 100);
+}
+
+#[test]
+fn logs_are_kept_when_the_scores_file_is_unreadable() {
+    let dir = ScratchDir::new("store-corrupt", &[]);
+    store_score(&dir.0, entry("2026-10-16T10:00:00+02:00"), &keystrokes(2));
+    std::fs::write(dir.0.join("scores.json"), "[{ not json").unwrap();
+
+    store_score(&dir.0, entry("2026-10-17T10:00:00+02:00"), &keystrokes(1));
+    assert_eq!(std::fs::read_dir(dir.0.join("keystrokes")).unwrap().count(), 2);
+}
+
+#[test]
+fn logs_inside_old_score_files_move_out_on_save() {
+    let dir = ScratchDir::new("store-migrate", &[]);
+    let mut old = entry("2026-01-01T09:00:00+01:00");
+    old.keystrokes = keystrokes(3);
+    let json = serde_json::to_string(&[old]).unwrap();
+    // Written the way scores were before logs had their own files
+    let json = json.replace(r#""key_stats""#, &format!(
+        r#""keystrokes":{},"key_stats""#,
+        serde_json::to_string(&keystrokes(3)).unwrap()
+    ));
+    std::fs::write(dir.0.join("scores.json"), json).unwrap();
+    assert_eq!(read_keystrokes(&dir.0, &read_scores(&dir.0)[0]), keystrokes(3));
+
+    store_score(&dir.0, entry("2026-10-17T10:00:00+02:00"), &[]);
+    let json = std::fs::read_to_string(dir.0.join("scores.json")).unwrap();
+    assert!(!json.contains("timestamp_ms"));
+    let scores = read_scores(&dir.0);
+    assert_eq!(read_keystrokes(&dir.0, &scores[1]), keystrokes(3));
+}
+
+#[test]
+fn old_backspace_toggle_loads_as_confidence() {
+    let settings = |backspace: bool| {
+        let mut json = serde_json::to_value(TestConfig::default()).unwrap();
+        json.as_object_mut().unwrap().remove("confidence");
+        json["backspace"] = backspace.into();
+        settings_from_json(&json.to_string())
+    };
+    assert_eq!(settings(false).confidence, Confidence::Max);
+    assert_eq!(settings(true).confidence, Confidence::Off);
+
+    let saved = serde_json::to_string(&settings(false)).unwrap();
+    assert!(!saved.contains("backspace"));
+    assert_eq!(settings_from_json(&saved).confidence, Confidence::Max);
+}
@@ -0,0 +1,150 @@
+mod common;
+
+use buddytype::data::themes::*;
+use ratatui::style::Color;
+use common::ScratchDir;
+
+const TEAM_JSON: &str = r##"{
+  "bg": "#1e1e2e", "text": "#cdd6f4", "textDim": "#6c7086", "correct": "#cdd6f4",
+  "incorrect": "#f38ba8", "extra": "#eba0ac", "cursor": "#f5e0dc", "accent": "#89b4fa",
+  "stats": "#6c7086"
+}"##;
+
+const TEAM_TOML: &str = r##"
+# Our terminal palette
+bg = "#1e1e2e"
+text = "#cdd6f4"
+text_dim = '#6c7086'
+correct = "#cdd6f4"
+incorrect = "#f38ba8"   # red
+extra = "#eba0ac"
+cursor = "#f5e0dc"
+accent = "#89b4fa"
+stats = "#6c7086"
+"##;
+
+// ── Colors ──────────────────────────────────────────────────────
+
+#[test]
+fn parses_long_short_and_alpha_hex_colors() {
+    assert_eq!(parse_color("#1e1e2e"), Ok(Color::Rgb(0x1e, 0x1e, 0x2e)));
+    assert_eq!(parse_color("#333"), Ok(Color::Rgb(0x33, 0x33, 0x33)));
+    assert_eq!(parse_color("#1c82adc4"), Ok(Color::Rgb(0x1c, 0x82, 0xad)));
+}
+
+#[test]
+fn rejects_malformed_colors() {
+    for bad in ["", "#12", "#12345", "#zz0000", "red", "#1e1e2e0"] {
+        assert!(parse_color(bad).is_err(), "{bad}");
+    }
+    assert_eq!(parse_hex("#nope"), Color::Reset);
+}
+
+#[test]
+fn bundled_themes_have_valid_colors() {
+    for name in get_theme_names() {
+        let invalid = invalid_colors(&get_theme(&name));
+        assert!(invalid.is_empty(), "{name}: {invalid:?}");
+    }
+}
+
+// ── Theme files ─────────────────────────────────────────────────
+
+#[test]
+fn toml_and_json_themes_read_the_same() {
+    let json = parse_theme(TEAM_JSON, false).unwrap();
+    let toml = parse_theme(TEAM_TOML, true).unwrap();
+    assert_eq!(json.colors(), toml.colors());
+}
+
+#[test]
+fn toml_themes_only_take_string_keys() {
+    assert!(parse_theme("[colors]\nbg = \"#000\"", true).is_err());
+    assert!(parse_theme("bg = 12", true).is_err());
+}
+
+#[test]
+fn toml_themes_take_any_string_syntax() {
+    let toml = r##"
+bg = '#1e1e2e'   # literal string, then a comment
+"text" = "#cdd6f4"
+textDim = """#6c7086"""
+correct = "#cdd6f4" # another comment
+incorrect = "#f38ba8"
+extra = "#eba0ac"
+cursor = "#f5e0dc"
+accent = "#89b4fa"
+stats = "#6c7086"
+"##;
+    let theme = parse_theme(toml, true).unwrap();
+    assert_eq!(theme.colors(), parse_theme(TEAM_JSON, false).unwrap().colors());
+}
+
+#[test]
+fn loads_theme_files_named_after_the_file() {
+    let dir = ScratchDir::new(
+        "themes",
+        &[("team.json", TEAM_JSON), ("team_light.toml", TEAM_TOML)],
+    );
+    let (themes, errors) = load_theme_files(&dir.0);
+    assert!(errors.is_empty(), "{errors:?}");
+    let names: Vec<&str> = themes.iter().map(|(_, theme)| theme.name.as_str()).collect();
+    assert_eq!(names, vec!["team", "team_light"]);
+}
+
+#[test]
+fn invalid_colors_are_reported() {
+    let broken = TEAM_JSON.replace("#89b4fa", "#89b4fz").replace("#f5e0dc", "pink");
+    let dir = ScratchDir::new(
+        "bad-themes",
+        &[("broken.json", &broken), ("missing.toml", "bg = \"#000000\"")],
+    );
+    let (themes, errors) = load_theme_files(&dir.0);
+    assert!(themes.is_empty());
+    assert_eq!(
+        errors[0],
+        "broken.json: cursor \"pink\" is not a hex color, accent \"#89b4fz\" is not a hex color"
+    );
+    assert!(errors[1].starts_with("missing.toml: missing field"), "{errors:?}");
+}
+
+#[test]
+fn unknown_themes_are_an_error() {
+    assert!(find_theme("dark").is_ok());
+    assert!(find_theme("no-such-theme").is_err());
+    assert_eq!(get_theme("no-such-theme").name, DEFAULT_THEME);
+}
+
+// ── Saving ──────────────────────────────────────────────────────
+
+#[test]
+fn saved_themes_load_back_the_same() {
+    let dir = ScratchDir::new("save-themes", &[]);
+    let mut theme = get_theme("dark").as_ref().clone();
+    theme.accent = "#89b4fa".into();
+    let path = write_theme_file(&dir.0.join("themes"), "dark_custom", &theme).unwrap();
+    assert!(path.ends_with("themes/dark_custom.json"));
+
+    let (themes, errors) = load_theme_files(&dir.0.join("themes"));
+    assert!(errors.is_empty(), "{errors:?}");
+    assert_eq!(themes.len(), 1);
+    assert_eq!(themes[0].0, "dark_custom");
+    assert_eq!(themes[0].1.name, "dark_custom");
+    assert_eq!(themes[0].1.colors(), theme.colors());
+}
+
+#[test]
+fn saving_checks_name_and_colors() {
+    let dir = ScratchDir::new("save-bad-themes", &[]);
+    let mut theme = get_theme("dark").as_ref().clone();
+    assert!(write_theme_file(&dir.0, "", &theme).is_err());
+    assert!(write_theme_file(&dir.0, "../escape", &theme).is_err());
+    assert!(check_theme_name("my-theme_2").is_ok());
+    assert!(check_theme_name("dark").is_err());
+    assert!(write_theme_file(&dir.0, "nord", &theme).is_err());
+
+    *theme.colors_mut()[4] = "#ca47".into();
+    let err = write_theme_file(&dir.0, "half_typed", &theme).unwrap_err();
+    assert_eq!(err, "incorrect \"#ca47\" is not a hex color");
+    assert!(!dir.0.join("half_typed.json").exists());
+}
@@ -161,3 +161,17 @@ fn get_remaining_clamps_to_zero() {
     timer.elapsed_ms = 35000.0;
     assert_eq!(get_remaining_seconds(&timer), 0.0);
 }
+
+#[test]
+fn elapsed_ms_at_measures_from_start_time() {
+    let now = Instant::now();
+    let timer = start_timer(create_timer(Some(30)), now);
+    let elapsed = get_elapsed_ms_at(&timer, now + Duration::from_millis(1500));
+    assert!((elapsed - 1500.0).abs() < 0.01);
+}
+
+#[test]
+fn elapsed_ms_at_is_zero_before_start() {
+    let timer = create_timer(Some(30));
+    assert_eq!(get_elapsed_ms_at(&timer, Instant::now()), 0.0);
+}
@@ -0,0 +1,178 @@
+use buddytype::data::languages::get_language;
+use buddytype::engine::word_filter::*;
+use buddytype::engine::word_generator::generate_words;
+use buddytype::types::{
+    GeneratorConfig, KeySet, KeyboardLayout, Relaxation, WordFilter, WordListSize,
+};
+
+fn list(words: &[&str]) -> Vec<String> {
+    words.iter().map(|w| w.to_string()).collect()
+}
+
+// ── Key sets ────────────────────────────────────────────────────
+
+#[test]
+fn key_sets_follow_the_layout() {
+    let home = key_set_chars(KeyboardLayout::Qwerty, KeySet::HomeRow);
+    assert!(home.starts_with("asdfghjkl"));
+    let home = key_set_chars(KeyboardLayout::Colemak, KeySet::HomeRow);
+    assert!(home.starts_with("arstdhneio"));
+    let left = key_set_chars(KeyboardLayout::Dvorak, KeySet::LeftHand);
+    assert!(left.contains('a') && left.contains('u') && !left.contains('h'));
+}
+
+#[test]
+fn hands_split_every_row() {
+    let left = key_set_chars(KeyboardLayout::Qwerty, KeySet::LeftHand);
+    let right = key_set_chars(KeyboardLayout::Qwerty, KeySet::RightHand);
+    for c in "qwertasdfgzxcvb".chars() {
+        assert!(left.contains(c) && !right.contains(c), "{c}");
+    }
+    for c in "yuiophjklnm".chars() {
+        assert!(right.contains(c), "{c}");
+    }
+}
+
+// ── Matching ────────────────────────────────────────────────────
+
+#[test]
+fn length_bounds_count_characters() {
+    let filter = WordFilter {
+        min_length: Some(3),
+        max_length: Some(4),
+        ..WordFilter::default()
+    };
+    assert!(!matches_filter("an", &filter));
+    assert!(matches_filter("née", &filter));
+    assert!(matches_filter("fall", &filter));
+    assert!(!matches_filter("falls", &filter));
+}
+
+#[test]
+fn required_characters_need_any_one_of_them() {
+    let filter = WordFilter {
+        required: "jq".into(),
+        ..WordFilter::default()
+    };
+    assert!(matches_filter("just", &filter));
+    assert!(matches_filter("Quiet", &filter));
+    assert!(!matches_filter("word", &filter));
+}
+
+#[test]
+fn allowed_keys_reject_any_other_character() {
+    let filter = WordFilter {
+        allowed: Some(KeySet::HomeRow),
+        ..WordFilter::default()
+    };
+    assert!(matches_filter("flask", &filter));
+    assert!(matches_filter("Dad", &filter));
+    assert!(!matches_filter("dash-", &filter));
+    assert!(!matches_filter("glass!", &filter));
+    assert!(!matches_filter("hello", &filter));
+}
+
+// ── Filtering ───────────────────────────────────────────────────
+
+#[test]
+fn filtering_keeps_frequency_order() {
+    let words = list(&["the", "a", "sad", "of", "lad", "has", "ask", "flag", "glass", "dad"]);
+    let filter = WordFilter {
+        allowed: Some(KeySet::HomeRow),
+        ..WordFilter::default()
+    };
+    let (filtered, relaxed) = filter_words(&words, true, &filter);
+    assert_eq!(filtered.to_vec(), list(&["a", "sad", "lad", "has", "ask", "flag", "glass", "dad"]));
+    assert!(relaxed.is_empty());
+}
+
+#[test]
+fn too_few_matches_relax_the_filter_in_order() {
+    let words = list(&["a", "sad", "has", "ask", "dad", "the", "of", "you", "we", "they"]);
+    let filter = WordFilter {
+        min_length: Some(6),
+        required: "s".into(),
+        allowed: Some(KeySet::HomeRow),
+        ..WordFilter::default()
+    };
+    // Home row words with an s: sad, has, ask — still too few
+    let (filtered, relaxed) = filter_words(&words, true, &filter);
+    assert_eq!(relaxed, vec![Relaxation::Length, Relaxation::Required]);
+    assert_eq!(filtered.to_vec(), list(&["a", "sad", "has", "ask", "dad"]));
+}
+
+#[test]
+fn hopeless_filters_fall_back_to_the_whole_list() {
+    let words = list(&["the", "of", "and"]);
+    let filter = WordFilter {
+        required: "z".into(),
+        ..WordFilter::default()
+    };
+    let (filtered, relaxed) = filter_words(&words, true, &filter);
+    assert_eq!(filtered.len(), 3);
+    assert_eq!(relaxed, vec![Relaxation::Required]);
+}
+
+#[test]
+fn rank_windows_slice_the_list() {
+    let words = list(&["a", "b", "c", "d", "e", "f", "g", "h", "i", "j"]);
+    let filter = WordFilter {
+        ranks: Some((2, 8)),
+        ..WordFilter::default()
+    };
+    let (filtered, relaxed) = filter_words(&words, true, &filter);
+    assert_eq!(filtered.to_vec(), list(&["c", "d", "e", "f", "g", "h"]));
+    assert!(relaxed.is_empty());
+
+    // A window reaching past the end keeps what there is
+    let filter = WordFilter {
+        ranks: Some((4, 1000)),
+        ..WordFilter::default()
+    };
+    assert_eq!(filter_words(&words, true, &filter).0.len(), 6);
+}
+
+#[test]
+fn rank_windows_need_a_ranked_list_that_reaches_them() {
+    let words = list(&["a", "b", "c", "d", "e", "f"]);
+    let filter = WordFilter {
+        ranks: Some((0, 3)),
+        ..WordFilter::default()
+    };
+    let (filtered, relaxed) = filter_words(&words, false, &filter);
+    assert_eq!((filtered.len(), relaxed), (6, vec![Relaxation::Ranks]));
+
+    let filter = WordFilter {
+        ranks: Some((200, 1000)),
+        ..WordFilter::default()
+    };
+    let (filtered, relaxed) = filter_words(&words, true, &filter);
+    assert_eq!((filtered.len(), relaxed), (6, vec![Relaxation::Ranks]));
+}
+
+#[test]
+fn generated_words_respect_the_filter() {
+    let filter = WordFilter {
+        max_length: Some(4),
+        allowed: Some(KeySet::LeftHand),
+        ..WordFilter::default()
+    };
+    // English has enough short left-hand words not to relax anything
+    let (_, relaxed) = filter_words(&get_language("english").words, true, &filter);
+    assert!(relaxed.is_empty());
+
+    let words = generate_words(&GeneratorConfig {
+        language: "english".into(),
+        count: 100,
+        punctuation: false,
+        numbers: false,
+        seed: Some(4),
+        targets: Vec::new(),
+        list_size: WordListSize::default(),
+        filter,
+    });
+    let left = key_set_chars(KeyboardLayout::Qwerty, KeySet::LeftHand);
+    for word in &words {
+        assert!(word.chars().count() <= 4 && word.chars().all(|c| left.contains(c)), "{word}");
+    }
+}
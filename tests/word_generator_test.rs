@@ -1,6 +1,10 @@
-use buddytype::data::languages::get_language;
-use buddytype::engine::word_generator::generate_words;
-use buddytype::types::GeneratorConfig;
+use buddytype::data::languages::{
+    available_list_sizes, get_available_languages, get_language, get_word_list,
+};
+use buddytype::engine::word_generator::{
+    generate_more_words, generate_words, is_contraction_of, random_seed, MAX_RANDOM_SEED,
+};
+use buddytype::types::{GeneratorConfig, WordFilter, WordListSize};
 
 #[test]
 fn loads_english_word_list() {
@@ -16,6 +20,10 @@ fn generates_requested_number_of_words() {
         count: 25,
         punctuation: false,
         numbers: false,
+        seed: None,
+        targets: Vec::new(),
+        list_size: WordListSize::default(),
+        filter: WordFilter::default(),
     });
     assert_eq!(words.len(), 25);
 }
@@ -28,6 +36,10 @@ fn all_words_come_from_word_list() {
         count: 50,
         punctuation: false,
         numbers: false,
+        seed: None,
+        targets: Vec::new(),
+        list_size: WordListSize::default(),
+        filter: WordFilter::default(),
     });
     for word in &words {
         assert!(
@@ -47,6 +59,10 @@ fn applies_punctuation_when_enabled() {
             count: 50,
             punctuation: true,
             numbers: false,
+            seed: None,
+            targets: Vec::new(),
+            list_size: WordListSize::default(),
+            filter: WordFilter::default(),
         });
         let joined = words.join(" ");
         if joined.contains('.') || joined.contains(',') {
@@ -66,6 +82,10 @@ fn includes_numbers_when_enabled() {
             count: 50,
             punctuation: false,
             numbers: true,
+            seed: None,
+            targets: Vec::new(),
+            list_size: WordListSize::default(),
+            filter: WordFilter::default(),
         });
         if words.iter().any(|w| w.chars().all(|c| c.is_ascii_digit())) {
             has_numbers = true;
@@ -82,6 +102,10 @@ fn generates_exactly_1_word() {
         count: 1,
         punctuation: false,
         numbers: false,
+        seed: None,
+        targets: Vec::new(),
+        list_size: WordListSize::default(),
+        filter: WordFilter::default(),
     });
     assert_eq!(words.len(), 1);
     assert!(!words[0].is_empty());
@@ -94,6 +118,10 @@ fn generates_100_words() {
         count: 100,
         punctuation: false,
         numbers: false,
+        seed: None,
+        targets: Vec::new(),
+        list_size: WordListSize::default(),
+        filter: WordFilter::default(),
     });
     assert_eq!(words.len(), 100);
 }
@@ -105,6 +133,10 @@ fn works_with_code_languages() {
         count: 20,
         punctuation: false,
         numbers: false,
+        seed: None,
+        targets: Vec::new(),
+        list_size: WordListSize::default(),
+        filter: WordFilter::default(),
     });
     assert_eq!(words.len(), 20);
     for w in &words {
@@ -119,10 +151,19 @@ fn works_with_non_english_languages() {
         count: 15,
         punctuation: false,
         numbers: false,
+        seed: None,
+        targets: Vec::new(),
+        list_size: WordListSize::default(),
+        filter: WordFilter::default(),
     });
     assert_eq!(words.len(), 15);
 }
 
+/// The first letter of a token, past any opening quote, parenthesis or `¿`.
+fn first_letter(token: &str) -> char {
+    token.chars().find(|c| c.is_alphanumeric()).unwrap()
+}
+
 #[test]
 fn punctuation_capitalizes_first_word() {
     let mut has_capital = false;
@@ -132,9 +173,12 @@ fn punctuation_capitalizes_first_word() {
             count: 10,
             punctuation: true,
             numbers: false,
+            seed: None,
+            targets: Vec::new(),
+            list_size: WordListSize::default(),
+            filter: WordFilter::default(),
         });
-        let first_char = words[0].chars().next().unwrap();
-        if first_char.is_uppercase() {
+        if first_letter(&words[0]).is_uppercase() {
             has_capital = true;
             break;
         }
@@ -149,7 +193,398 @@ fn no_numbers_when_numbers_disabled() {
         count: 100,
         punctuation: false,
         numbers: false,
+        seed: None,
+        targets: Vec::new(),
+        list_size: WordListSize::default(),
+        filter: WordFilter::default(),
     });
     let has_numbers = words.iter().any(|w| w.chars().all(|c| c.is_ascii_digit()));
     assert!(!has_numbers);
 }
+
+// ── Continued word streams ──────────────────────────────────────
+
+#[test]
+fn generates_more_words_of_requested_count() {
+    let config = GeneratorConfig {
+        language: "english".into(),
+        count: 30,
+        punctuation: false,
+        numbers: false,
+        seed: None,
+        targets: Vec::new(),
+        list_size: WordListSize::default(),
+        filter: WordFilter::default(),
+    };
+    let first = generate_words(&config);
+    let more = generate_more_words(&config, &first);
+    assert_eq!(more.len(), 30);
+}
+
+#[test]
+fn continued_chunk_starts_new_sentence_after_period() {
+    let config = GeneratorConfig {
+        language: "english".into(),
+        count: 5,
+        punctuation: true,
+        numbers: false,
+        seed: None,
+        targets: Vec::new(),
+        list_size: WordListSize::default(),
+        filter: WordFilter::default(),
+    };
+    let more = generate_more_words(&config, &["the".into(), "end.".into()]);
+    assert!(first_letter(&more[0]).is_uppercase());
+}
+
+#[test]
+fn continued_chunk_keeps_running_sentence_lowercase() {
+    let config = GeneratorConfig {
+        language: "english".into(),
+        count: 5,
+        punctuation: true,
+        numbers: false,
+        seed: None,
+        targets: Vec::new(),
+        list_size: WordListSize::default(),
+        filter: WordFilter::default(),
+    };
+    for _ in 0..20 {
+        let more = generate_more_words(&config, &["The".into(), "middle".into()]);
+        assert!(first_letter(&more[0]).is_lowercase());
+    }
+}
+
+// ── Seeds ───────────────────────────────────────────────────────
+
+fn seeded(seed: u64) -> GeneratorConfig {
+    GeneratorConfig {
+        language: "english".into(),
+        count: 50,
+        punctuation: true,
+        numbers: true,
+        seed: Some(seed),
+        targets: Vec::new(),
+        list_size: WordListSize::default(),
+        filter: WordFilter::default(),
+    }
+}
+
+#[test]
+fn same_seed_generates_same_words() {
+    assert_eq!(generate_words(&seeded(42)), generate_words(&seeded(42)));
+}
+
+#[test]
+fn different_seeds_generate_different_words() {
+    assert_ne!(generate_words(&seeded(1)), generate_words(&seeded(2)));
+}
+
+#[test]
+fn seeded_word_stream_is_reproducible() {
+    let first = generate_words(&seeded(7));
+    let more = generate_more_words(&seeded(7), &first);
+    assert_eq!(more, generate_more_words(&seeded(7), &first));
+    assert_ne!(more, first);
+}
+
+#[test]
+fn random_seeds_stay_shareable() {
+    for _ in 0..100 {
+        assert!(random_seed() < MAX_RANDOM_SEED);
+    }
+}
+
+// ── Punctuation profiles ────────────────────────────────────────
+
+fn punctuated(language: &str, seed: u64) -> Vec<String> {
+    generate_words(&GeneratorConfig {
+        language: language.into(),
+        count: 200,
+        punctuation: true,
+        numbers: false,
+        seed: Some(seed),
+        targets: Vec::new(),
+        list_size: WordListSize::default(),
+        filter: WordFilter::default(),
+    })
+}
+
+#[test]
+fn languages_without_a_profile_use_defaults() {
+    let profile = &get_language("code_rust").punctuation;
+    assert_eq!(profile.quotes, ("\"".to_string(), "\"".to_string()));
+    assert!(profile.hyphenation);
+    assert!(!profile.spaced_marks && !profile.inverted_marks);
+    assert!(profile.contractions.is_empty());
+}
+
+#[test]
+fn profiles_load_from_language_files() {
+    assert!(get_language("spanish").punctuation.inverted_marks);
+    assert!(get_language("french").punctuation.spaced_marks);
+    assert!(!get_language("german").punctuation.nouns.is_empty());
+    assert!(!get_language("english").punctuation.contractions.is_empty());
+}
+
+#[test]
+fn punctuated_chunks_keep_requested_count() {
+    for language in ["english", "french", "spanish", "german"] {
+        for seed in 0..20 {
+            assert_eq!(punctuated(language, seed).len(), 200, "{language} seed {seed}");
+        }
+    }
+}
+
+#[test]
+fn punctuation_uses_questions_and_exclamations() {
+    let text = (0..5).flat_map(|s| punctuated("english", s)).collect::<Vec<_>>().join(" ");
+    assert!(text.contains('?'));
+    assert!(text.contains('!'));
+    assert!(text.contains(':') || text.contains(';'));
+}
+
+#[test]
+fn quotes_and_parentheses_are_balanced() {
+    for seed in 0..50 {
+        let text = punctuated("english", seed).join(" ");
+        assert_eq!(text.matches('"').count() % 2, 0, "{text}");
+        assert_eq!(text.matches('(').count(), text.matches(')').count(), "{text}");
+    }
+}
+
+#[test]
+fn english_uses_contractions() {
+    let text = (0..10).flat_map(|s| punctuated("english", s)).collect::<Vec<_>>().join(" ");
+    assert!(text.contains('\''));
+}
+
+#[test]
+fn contractions_come_from_their_own_word() {
+    assert!(is_contraction_of("don't", "do"));
+    assert!(is_contraction_of("c'est", "ce"));
+    assert!(is_contraction_of("quest'anno", "questo"));
+    assert!(!is_contraction_of("c'est", "est"));
+    assert!(!is_contraction_of("qu'il", "il"));
+    assert!(!is_contraction_of("won't", "will"));
+}
+
+#[test]
+fn bundled_contractions_are_vetted() {
+    for name in get_available_languages() {
+        let language = get_language(name);
+        for (word, form) in &language.punctuation.contractions {
+            assert!(is_contraction_of(form, word), "{name}: {word} -> {form}");
+            assert!(language.words.contains(word), "{name}: {word} is not in the list");
+        }
+    }
+}
+
+#[test]
+fn french_contractions_replace_only_their_word() {
+    let forms: Vec<&String> = get_language("french")
+        .punctuation
+        .contractions
+        .iter()
+        .map(|(_, form)| form)
+        .collect();
+    let words: Vec<String> = (0..20).flat_map(|s| punctuated("french", s)).collect();
+    let contracted: Vec<String> = words
+        .iter()
+        .filter(|w| w.contains('\''))
+        .map(|w| w.trim_matches(|c: char| !c.is_alphabetic()).to_lowercase())
+        .collect();
+    assert!(!contracted.is_empty());
+    for word in contracted {
+        assert!(forms.contains(&&word), "{word}");
+    }
+}
+
+#[test]
+fn spanish_questions_open_with_inverted_marks() {
+    for seed in 0..20 {
+        let text = punctuated("spanish", seed).join(" ");
+        // The chunk may stop in the middle of a sentence
+        let text = &text[..text.rfind(['.', '?', '!']).unwrap() + 1];
+        assert_eq!(text.matches('¿').count(), text.matches('?').count(), "{text}");
+        assert_eq!(text.matches('¡').count(), text.matches('!').count(), "{text}");
+    }
+}
+
+#[test]
+fn french_sets_off_marks_with_a_space() {
+    let words: Vec<String> = (0..10).flat_map(|s| punctuated("french", s)).collect();
+    assert!(words.iter().any(|w| w == "?" || w == "!"));
+    // Only the last token of a chunk may carry its mark attached
+    let attached = words.iter().filter(|w| w.len() > 1 && w.ends_with('?')).count();
+    assert!(attached <= 10);
+}
+
+#[test]
+fn german_hyphenates_only_nouns() {
+    let words: Vec<String> = (0..30).flat_map(|s| punctuated("german", s)).collect();
+    let compounds: Vec<&String> = words.iter().filter(|w| w.contains('-')).collect();
+    assert!(!compounds.is_empty());
+    for compound in compounds {
+        for part in compound.split('-') {
+            assert!(first_letter(part).is_uppercase(), "{compound}");
+        }
+    }
+}
+
+#[test]
+fn german_nouns_are_capitalized_and_pronouns_are_not_nouns() {
+    let nouns = &get_language("german").punctuation.nouns;
+    assert!(!nouns.iter().any(|n| n == "Sie"));
+    for punctuation in [false, true] {
+        let words = generate_words(&GeneratorConfig {
+            language: "german".into(),
+            count: 2000,
+            punctuation,
+            numbers: false,
+            seed: Some(5),
+            targets: Vec::new(),
+            list_size: WordListSize::default(),
+            filter: WordFilter::default(),
+        });
+        for word in &words {
+            for part in word.split('-') {
+                let bare = part.trim_matches(|c: char| !c.is_alphabetic());
+                if let Some(noun) = nouns.iter().find(|n| n.to_lowercase() == bare.to_lowercase()) {
+                    assert_eq!(bare, noun);
+                }
+            }
+            if word.contains('-') {
+                assert!(!word.contains("Sie"), "{word}");
+            }
+        }
+    }
+}
+
+#[test]
+fn continued_question_keeps_its_inverted_mark() {
+    let config = GeneratorConfig {
+        language: "spanish".into(),
+        count: 100,
+        punctuation: true,
+        numbers: false,
+        seed: Some(3),
+        targets: Vec::new(),
+        list_size: WordListSize::default(),
+        filter: WordFilter::default(),
+    };
+    let preceding = vec!["¿Como".to_string(), "su".to_string()];
+    let more = generate_more_words(&config, &preceding);
+    let first_end = more.iter().find(|w| w.ends_with(['.', '?', '!'])).unwrap();
+    assert!(first_end.ends_with('?'), "{more:?}");
+}
+
+// ── Adaptive targets ────────────────────────────────────────────
+
+fn targeted(targets: &[&str], seed: u64) -> GeneratorConfig {
+    GeneratorConfig {
+        language: "english".into(),
+        count: 400,
+        punctuation: false,
+        numbers: false,
+        seed: Some(seed),
+        targets: targets.iter().map(|t| t.to_string()).collect(),
+        list_size: WordListSize::default(),
+        filter: WordFilter::default(),
+    }
+}
+
+#[test]
+fn targets_are_drawn_more_often() {
+    let share = |targets: &[&str]| {
+        let words = generate_words(&targeted(targets, 5));
+        words.iter().filter(|w| w.contains('k') || w.contains("wh")).count()
+    };
+    let plain = share(&[]);
+    let biased = share(&["k", "wh"]);
+    assert!(biased > plain * 2, "{plain} vs {biased}");
+}
+
+#[test]
+fn targeted_words_are_reproducible() {
+    let config = targeted(&["q"], 9);
+    assert_eq!(generate_words(&config), generate_words(&config));
+}
+
+// ── Word list sizes ─────────────────────────────────────────────
+
+fn sized(list_size: WordListSize, ranks: Option<(usize, usize)>) -> GeneratorConfig {
+    GeneratorConfig {
+        language: "english".into(),
+        count: 200,
+        punctuation: false,
+        numbers: false,
+        seed: Some(11),
+        targets: Vec::new(),
+        list_size,
+        filter: WordFilter {
+            ranks,
+            ..WordFilter::default()
+        },
+    }
+}
+
+#[test]
+fn bigger_lists_are_variants_of_their_language() {
+    let big = get_word_list("english", WordListSize::OneK).unwrap();
+    assert_eq!(big.words.len(), 1000);
+    assert_eq!(big.words[..10], get_language("english").words[..10]);
+    assert!(available_list_sizes("english").contains(&WordListSize::OneK));
+    assert_eq!(available_list_sizes("code_rust"), vec![WordListSize::Base]);
+    assert!(!get_available_languages().contains(&"english_1k"));
+}
+
+#[test]
+fn every_language_comes_in_every_size() {
+    for language in ["english", "french", "german", "italian", "portuguese", "spanish"] {
+        assert_eq!(available_list_sizes(language), WordListSize::ALL, "{language}");
+        let ten_k = &get_word_list(language, WordListSize::TenK).unwrap().words;
+        assert_eq!(ten_k.len(), 10_000, "{language}");
+        for (size, len) in [(WordListSize::OneK, 1000), (WordListSize::FiveK, 5000)] {
+            let list = get_word_list(language, size).unwrap();
+            assert_eq!(list.words[..], ten_k[..len], "{language} {}", size.label());
+        }
+    }
+}
+
+#[test]
+fn list_sizes_are_saved_by_label() {
+    for size in WordListSize::ALL {
+        let json = serde_json::to_string(&size).unwrap();
+        assert_eq!(json, format!("\"{}\"", size.label()));
+        assert_eq!(serde_json::from_str::<WordListSize>(&json).unwrap(), size);
+    }
+}
+
+#[test]
+fn rank_window_draws_only_from_its_slice() {
+    let big = get_word_list("english", WordListSize::OneK).unwrap();
+    let window = &big.words[200..1000];
+    for word in generate_words(&sized(WordListSize::OneK, Some((200, 1000)))) {
+        assert!(window.contains(&word), "{word}");
+    }
+}
+
+#[test]
+fn deep_rank_window_draws_from_the_10k_list() {
+    let big = get_word_list("english", WordListSize::TenK).unwrap();
+    let window = &big.words[5000..10_000];
+    for word in generate_words(&sized(WordListSize::TenK, Some((5000, 10_000)))) {
+        assert!(window.contains(&word), "{word}");
+    }
+}
+
+#[test]
+fn missing_list_sizes_fall_back_to_the_language_list() {
+    let rust = get_language("code_rust");
+    let mut config = sized(WordListSize::TenK, None);
+    config.language = "code_rust".into();
+    for word in generate_words(&config) {
+        assert!(rust.words.contains(&word), "{word}");
+    }
+}